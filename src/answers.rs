@@ -0,0 +1,189 @@
+use crate::error::{Error, ParseError, Result};
+use crate::Day;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Recorded puzzle answers, read from a list of `dayN.partM = value` lines.
+///
+/// Values spanning several lines are written between `"""` lines:
+///
+/// ```text
+/// day10.part2 = """
+/// ##..##..
+/// ###...##
+/// """
+/// ```
+///
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u32, u32), String>,
+}
+
+fn parse_key<'a>(input: &'a str, key: &'a str) -> Result<(u32, u32), ParseError> {
+    let parse_number = |s: &'a str, prefix: &str| {
+        s.strip_prefix(prefix)
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| ParseError::at(input, s, format!("expected {prefix}<number>")))
+    };
+    let (day, part) = key
+        .split_once('.')
+        .ok_or_else(|| ParseError::at(input, key, "expected a key like day1.part2"))?;
+    Ok((parse_number(day, "day")?, parse_number(part, "part")?))
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Answers, ParseError> {
+        let mut answers = HashMap::new();
+        let mut lines = input.lines();
+        while let Some(line) = lines.next() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::at(input, line, "expected `dayN.partM = value`"))?;
+            let key = parse_key(input, key.trim())?;
+            let value = match value.trim() {
+                "\"\"\"" => {
+                    let mut value = Vec::new();
+                    loop {
+                        match lines.next() {
+                            Some("\"\"\"") => break,
+                            Some(line) => value.push(line),
+                            None => return Err(ParseError::new("unterminated multi-line answer")),
+                        }
+                    }
+                    value.join("\n")
+                }
+                value => value.to_string(),
+            };
+            if answers.insert(key, value).is_some() {
+                return Err(ParseError::at(input, line, "duplicate answer"));
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn load(path: &Path) -> Result<Answers> {
+        let input = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.into(),
+            source,
+        })?;
+        Ok(Answers::parse(&input)?)
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// The outcome of checking one part against its recorded answer.
+#[derive(Debug)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// There is no recorded answer to compare with.
+    Missing {
+        actual: String,
+    },
+    Error(Error),
+}
+
+/// Solve both parts of `day` and compare them with the recorded `answers`.
+pub fn verify(day: &Day, input: &str, answers: &Answers) -> [(u32, Verdict); 2] {
+    let solution = match (day.parse)(input) {
+        Ok(solution) => solution,
+        Err(e) => {
+            return [1, 2].map(|part| (part, Verdict::Error(Error::Parse(e.clone()))));
+        }
+    };
+    [1, 2].map(|part| {
+        let verdict = match solution.part(part).expect("day has two parts") {
+            Err(e) => Verdict::Error(e),
+            Ok(answer) => {
+                let actual = answer.to_string().trim_end_matches('\n').to_string();
+                match answers.get(day.day, part) {
+                    None => Verdict::Missing { actual },
+                    Some(expected) if expected == actual => Verdict::Pass,
+                    Some(expected) => Verdict::Fail {
+                        expected: expected.to_string(),
+                        actual,
+                    },
+                }
+            }
+        };
+        (part, verdict)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse(
+            "# A comment
+
+day1.part1 = 24000
+day10.part2 = \"\"\"
+##..
+..##
+\"\"\"
+",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("24000"));
+        assert_eq!(answers.get(10, 2), Some("##..\n..##"));
+        assert_eq!(answers.get(1, 2), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |input: &str| Answers::parse(input).unwrap_err();
+        let e = error("day1.part1 = 1\nday1.part1 = 2\n");
+        assert_eq!(e.message, "duplicate answer");
+        assert_eq!(e.location.unwrap().line, 2);
+        assert_eq!(
+            error("day1.part1 = \"\"\"\n1\n").message,
+            "unterminated multi-line answer"
+        );
+        assert_eq!(
+            error("day1.part1 1").message,
+            "expected `dayN.partM = value`"
+        );
+        assert_eq!(error("day1 = 1").message, "expected a key like day1.part2");
+        let e = error("day1.prt2 = 1");
+        assert_eq!(e.message, "expected part<number>");
+        assert_eq!(e.location.unwrap().column, 6);
+        assert_eq!(error("dayX.part1 = 1").message, "expected day<number>");
+    }
+
+    #[test]
+    fn verdicts() {
+        let day = crate::get_day(6).unwrap();
+        let answers = Answers::parse("day6.part1 = 7\nday6.part2 = 20").unwrap();
+        let [(1, part1), (2, part2)] = verify(day, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", &answers)
+        else {
+            panic!("expected parts 1 and 2");
+        };
+        assert!(matches!(part1, Verdict::Pass));
+        assert!(
+            matches!(part2, Verdict::Fail { expected, actual } if expected == "20" && actual == "19")
+        );
+
+        let [(_, part1), _] = verify(day, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", &Answers::default());
+        assert!(matches!(part1, Verdict::Missing { actual } if actual == "7"));
+
+        let [(_, part1), (_, part2)] = verify(day, "abc", &answers);
+        assert!(matches!(part1, Verdict::Error(Error::NoSolution(_))));
+        assert!(matches!(part2, Verdict::Error(Error::NoSolution(_))));
+        let [(_, part1), _] = verify(crate::get_day(1).unwrap(), "x", &answers);
+        assert!(matches!(part1, Verdict::Error(Error::Parse(_))));
+    }
+}
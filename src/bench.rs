@@ -0,0 +1,170 @@
+use crate::error::Result;
+use crate::input::InputProvider;
+use crate::Day;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Summary of the timings of one phase over all iterations.
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort_unstable();
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+        Stats {
+            mean: samples.iter().sum::<Duration>() / len as u32,
+            median,
+            min: samples[0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DayTimings {
+    pub day: u32,
+    pub iterations: usize,
+    pub read: Stats,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayTimings {
+    pub fn phases(&self) -> [(&'static str, Stats); 4] {
+        [
+            ("read", self.read),
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = black_box(f());
+    (res, start.elapsed())
+}
+
+/// Time reading the input from `inputs`, parsing it and solving both parts of
+/// `day`, each `iterations` times.
+pub fn bench(day: &Day, inputs: &dyn InputProvider, iterations: usize) -> Result<DayTimings> {
+    assert!(iterations > 0, "at least one iteration is needed");
+    let mut samples: [Vec<Duration>; 4] = Default::default();
+    for _ in 0..iterations {
+        let (input, elapsed) = time(|| inputs.input(day.day));
+        let input = input?;
+        samples[0].push(elapsed);
+
+        let (solution, elapsed) = time(|| (day.parse)(&input));
+        let solution = solution?;
+        samples[1].push(elapsed);
+
+        let (answer, elapsed) = time(|| solution.part1());
+        answer?;
+        samples[2].push(elapsed);
+
+        let (answer, elapsed) = time(|| solution.part2());
+        answer?;
+        samples[3].push(elapsed);
+    }
+    let [read, parse, part1, part2] = samples.map(Stats::from_samples);
+    Ok(DayTimings {
+        day: day.day,
+        iterations,
+        read,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+/// Format timings as a JSON array with one object per day and durations in
+/// nanoseconds.
+pub fn to_json(timings: &[DayTimings]) -> String {
+    let mut json = String::from("[");
+    for (i, t) in timings.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write!(
+            json,
+            "\n  {{\"day\": {}, \"iterations\": {}",
+            t.day, t.iterations
+        )
+        .unwrap();
+        for (phase, stats) in t.phases() {
+            write!(
+                json,
+                ", \"{phase}\": {{\"mean_ns\": {}, \"median_ns\": {}, \"min_ns\": {}}}",
+                stats.mean.as_nanos(),
+                stats.median.as_nanos(),
+                stats.min.as_nanos()
+            )
+            .unwrap();
+        }
+        json.push('}');
+    }
+    json.push_str("\n]");
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::MemoryProvider;
+
+    #[test]
+    fn bench_day() {
+        let mut inputs = MemoryProvider::default();
+        inputs.insert(6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let timings = bench(crate::get_day(6).unwrap(), &inputs, 3).unwrap();
+        assert_eq!((timings.day, timings.iterations), (6, 3));
+        assert!(bench(crate::get_day(7).unwrap(), &inputs, 1).is_err());
+    }
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(10), ms(3)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, Duration::from_micros(3500));
+        assert_eq!(stats.mean, Duration::from_micros(4500));
+    }
+
+    #[test]
+    fn json() {
+        let stats = Stats {
+            mean: Duration::from_nanos(3),
+            median: Duration::from_nanos(2),
+            min: Duration::from_nanos(1),
+        };
+        let timings = DayTimings {
+            day: 1,
+            iterations: 5,
+            read: stats,
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        };
+        let phase = "{\"mean_ns\": 3, \"median_ns\": 2, \"min_ns\": 1}";
+        assert_eq!(
+            to_json(&[timings]),
+            format!(
+                "[\n  {{\"day\": 1, \"iterations\": 5, \"read\": {phase}, \"parse\": {phase}, \
+                 \"part1\": {phase}, \"part2\": {phase}}}\n]"
+            )
+        );
+    }
+}
@@ -0,0 +1,708 @@
+use advent_of_code_2022::answers::{self, Answers, Verdict};
+use advent_of_code_2022::bench;
+use advent_of_code_2022::day10::config::CpuConfig;
+use advent_of_code_2022::day10::cpu::{parse_program, Cpu, CrtRenderer, Instruction};
+use advent_of_code_2022::day10::debugger::Debugger;
+use advent_of_code_2022::day10::export::{to_ansi, to_png, to_ppm, FrameRecorder, ImageOptions};
+use advent_of_code_2022::day11::dot::to_dot;
+use advent_of_code_2022::day11::simulation::{Relief, RoundStats};
+use advent_of_code_2022::day11::trace::TraceWriter;
+use advent_of_code_2022::day11::worry::Backend;
+use advent_of_code_2022::day11::Day11;
+use advent_of_code_2022::day12::rules::MoveRule;
+use advent_of_code_2022::day12::Day12;
+use advent_of_code_2022::input::{DirectoryProvider, FileProvider, InputProvider, StdinProvider};
+use advent_of_code_2022::scaffold;
+use advent_of_code_2022::{get_day, Day, Error, Solution, DAYS};
+use std::env;
+use std::fs;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "Usage:
+  aoc run <day> [--part <1|2>] [--input <path>|-] [--input-dir <dir>]
+  aoc run --all [--input-dir <dir>]
+  aoc verify [<day>] [--answers <path>] [--input-dir <dir>]
+  aoc bench [<day>] [--iterations <n>] [--json] [--input-dir <dir>]
+  aoc new <day> [--input-dir <dir>]
+  aoc debug [<device options>]   (step through day 10's program)
+  aoc crt [<device options>] [--ppm <path>] [--png <path>] [--frames <dir>] [--scale <n>]
+          (draw day 10's CRT in the terminal or as images)
+  aoc monkeys [--input <path>|-] [--input-dir <dir>] [--worry <u64|u128|residues|bigint>]
+          [--rounds <n>] [--relief <none|/n|%n|product>] [--top <k>] [--every <n>|--fast]
+          [--csv <path>] [--jsonl <path>] [--dot <path>]
+          (solve day 11 with another number type for worry levels, or simulate
+          other games, show statistics every n rounds and write every throw,
+          or draw who throws to whom)
+  aoc climb [--input <path>|-] [--input-dir <dir>] [--part <1|2>] [--max-climb <n>]
+          [--max-descent <n>] [--diagonal] [--cost <steps|climbing|step,climb,descent>]
+          [--visualize [--every <n>] [--delay <ms>] [--frames <dir>]]
+          (draw day 12's shortest path on the map, with other rules for moving,
+          or animate the search every n expanded squares)
+
+Device options: [--input <path>] [--input-dir <dir>] [--width <n>] [--height <n>]
+  [--sprite-radius <n>] [--samples <cycles>|<start>+<step>]
+
+Inputs are read from --input-dir, $AOC_INPUT_DIR, the input directory of this
+repository or $XDG_CACHE_HOME/advent-of-code-2022, whichever comes first.";
+
+const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers/answers.txt");
+
+struct RunArgs {
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    input_dir: Option<String>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut run_args = RunArgs {
+        day: None,
+        part: None,
+        input: None,
+        input_dir: None,
+    };
+    let mut all = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--part" => {
+                let part = args.next().ok_or("--part requires a value")?;
+                match part.parse() {
+                    Ok(part @ (1 | 2)) => run_args.part = Some(part),
+                    _ => return Err(format!("Invalid part: {part}")),
+                }
+            }
+            "--input" => {
+                let path = args.next().ok_or("--input requires a value")?;
+                run_args.input = Some(path.clone());
+            }
+            "--input-dir" => {
+                let dir = args.next().ok_or("--input-dir requires a value")?;
+                run_args.input_dir = Some(dir.clone());
+            }
+            day if run_args.day.is_none() && !day.starts_with('-') => {
+                let day = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+                run_args.day = Some(day);
+            }
+            arg => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+    match (all, run_args.day) {
+        (true, Some(_)) => Err("Give either a day or --all, not both".to_string()),
+        (false, None) => Err("Missing day".to_string()),
+        (true, None) if run_args.part.is_some() || run_args.input.is_some() => {
+            Err("--part and --input can not be combined with --all".to_string())
+        }
+        _ => Ok(run_args),
+    }
+}
+
+fn print_answer(day: u32, part: u32, answer: &str) {
+    let answer = answer.trim_end_matches('\n');
+    if answer.contains('\n') {
+        println!("Day {day} part {part}:\n{answer}");
+    } else {
+        println!("Day {day} part {part}: {answer}");
+    }
+}
+
+/// Where to read inputs from: `input` is a single file, or `-` for standard
+/// input, and otherwise they are read from a directory.
+fn input_provider(input: Option<&str>, input_dir: Option<&str>) -> Box<dyn InputProvider> {
+    match input {
+        Some("-") => Box::<StdinProvider>::default(),
+        Some(path) => Box::new(FileProvider::new(path)),
+        None => Box::new(DirectoryProvider::new(DirectoryProvider::default_dir(
+            input_dir.map(Path::new),
+        ))),
+    }
+}
+
+fn solve_day(day: &Day, args: &RunArgs, inputs: &dyn InputProvider) -> Result<(), Error> {
+    let input = inputs.input(day.day)?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let solution = (day.parse)(&input)?;
+    for part in parts {
+        let answer = solution
+            .part(part)
+            .expect("part is validated when parsing args")?;
+        print_answer(day.day, part, &answer.to_string());
+    }
+    Ok(())
+}
+
+fn run_day(day: &Day, args: &RunArgs, inputs: &dyn InputProvider) -> Result<(), String> {
+    solve_day(day, args, inputs).map_err(|e| format!("Day {}: {e}", day.day))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let run_args = parse_run_args(args)?;
+    let inputs = input_provider(run_args.input.as_deref(), run_args.input_dir.as_deref());
+    match run_args.day {
+        Some(day) => {
+            let day = get_day(day).ok_or_else(|| format!("No solution for day {day}"))?;
+            run_day(day, &run_args, inputs.as_ref())
+        }
+        None => {
+            // Keep going after a day fails, so it doesn't hide the later ones
+            let mut failed = Vec::new();
+            for day in DAYS {
+                if let Err(e) = run_day(day, &run_args, inputs.as_ref()) {
+                    eprintln!("{e}");
+                    failed.push(day.day.to_string());
+                }
+            }
+            if failed.is_empty() {
+                Ok(())
+            } else {
+                Err(format!("Failed days: {}", failed.join(", ")))
+            }
+        }
+    }
+}
+
+fn print_value(label: &str, value: &str) {
+    if value.contains('\n') {
+        println!("  {label}:\n{value}");
+    } else {
+        println!("  {label}: {value}");
+    }
+}
+
+fn verify(args: &[String]) -> Result<(), String> {
+    let mut day = None;
+    let mut answers_path = DEFAULT_ANSWERS.to_string();
+    let mut input_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                answers_path = args.next().ok_or("--answers requires a value")?.clone();
+            }
+            "--input-dir" => {
+                input_dir = Some(args.next().ok_or("--input-dir requires a value")?.as_str());
+            }
+            d if day.is_none() && !d.starts_with('-') => {
+                let d = d.parse().map_err(|_| format!("Invalid day: {d}"))?;
+                day = Some(get_day(d).ok_or_else(|| format!("No solution for day {d}"))?);
+            }
+            arg => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+    let days = match day {
+        Some(day) => std::slice::from_ref(day),
+        None => DAYS,
+    };
+    let answers = Answers::load(Path::new(&answers_path)).map_err(|e| e.to_string())?;
+    let inputs = input_provider(None, input_dir);
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in days {
+        let input = match inputs.input(day.day) {
+            Ok(input) => input,
+            Err(e) => {
+                failed += 2;
+                println!("Day {}: ERROR", day.day);
+                println!("  {e}");
+                continue;
+            }
+        };
+        for (part, verdict) in answers::verify(day, &input, &answers) {
+            match verdict {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("Day {} part {part}: pass", day.day);
+                }
+                Verdict::Fail { expected, actual } => {
+                    failed += 1;
+                    println!("Day {} part {part}: FAIL", day.day);
+                    print_value("expected", &expected);
+                    print_value("actual", &actual);
+                }
+                Verdict::Missing { actual } => {
+                    missing += 1;
+                    println!("Day {} part {part}: missing", day.day);
+                    print_value("actual", &actual);
+                }
+                Verdict::Error(e) => {
+                    failed += 1;
+                    println!("Day {} part {part}: ERROR", day.day);
+                    println!("  {e}");
+                }
+            }
+        }
+    }
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    if failed > 0 {
+        Err(format!(
+            "{failed} part(s) did not match the recorded answers"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let mut day = None;
+    let mut iterations = 10;
+    let mut json = false;
+    let mut input_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--input-dir" => {
+                input_dir = Some(args.next().ok_or("--input-dir requires a value")?.as_str());
+            }
+            "--iterations" => {
+                let n = args.next().ok_or("--iterations requires a value")?;
+                iterations = match n.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid number of iterations: {n}")),
+                };
+            }
+            d if day.is_none() && !d.starts_with('-') => {
+                let d = d.parse().map_err(|_| format!("Invalid day: {d}"))?;
+                day = Some(get_day(d).ok_or_else(|| format!("No solution for day {d}"))?);
+            }
+            arg => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+    let days = match day {
+        Some(day) => std::slice::from_ref(day),
+        None => DAYS,
+    };
+
+    let inputs = input_provider(None, input_dir);
+    let mut timings = Vec::new();
+    if !json {
+        println!(
+            "Day  Phase   {:>12}  {:>12}  {:>12}",
+            "Mean", "Median", "Min"
+        );
+    }
+    for day in days {
+        let t = bench::bench(day, inputs.as_ref(), iterations)
+            .map_err(|e| format!("Day {}: {e}", day.day))?;
+        if !json {
+            for (phase, stats) in t.phases() {
+                println!(
+                    "{:<4} {phase:<7} {:>12}  {:>12}  {:>12}",
+                    t.day,
+                    format!("{:.2?}", stats.mean),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.min)
+                );
+            }
+        }
+        timings.push(t);
+    }
+    if json {
+        println!("{}", bench::to_json(&timings));
+    }
+    Ok(())
+}
+
+fn new(args: &[String]) -> Result<(), String> {
+    let mut day = None;
+    let mut input_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                input_dir = Some(args.next().ok_or("--input-dir requires a value")?.as_str());
+            }
+            d if day.is_none() && !d.starts_with('-') => {
+                day = Some(d.parse().map_err(|_| format!("Invalid day: {d}"))?);
+            }
+            arg => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+    let day = day.ok_or("Missing day")?;
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let input_dir = DirectoryProvider::default_dir(input_dir.map(Path::new));
+    let written = scaffold::scaffold(root, &input_dir, day).map_err(|e| e.to_string())?;
+    for path in written {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Options for the commands that run day 10's device.
+#[derive(Default)]
+struct DeviceArgs<'a> {
+    input: Option<&'a str>,
+    input_dir: Option<&'a str>,
+    config: CpuConfig,
+}
+
+/// The value following option `arg`.
+fn option_value<'a>(
+    arg: &str,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Result<&'a str, String> {
+    args.next()
+        .map(String::as_str)
+        .ok_or(format!("{arg} requires a value"))
+}
+
+fn positive<T: std::str::FromStr + Default + PartialOrd>(arg: &str, n: &str) -> Result<T, String> {
+    match n.parse() {
+        Ok(n) if n > T::default() => Ok(n),
+        _ => Err(format!("Invalid {arg}: {n}")),
+    }
+}
+
+impl<'a> DeviceArgs<'a> {
+    /// Handle `arg` if it is a device option, taking its value from `value`.
+    /// Returns whether it was one.
+    fn parse_option(
+        &mut self,
+        arg: &str,
+        value: impl FnOnce() -> Result<&'a str, String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--input" => self.input = Some(value()?),
+            "--input-dir" => self.input_dir = Some(value()?),
+            "--width" => self.config.crt.width = positive(arg, value()?)?,
+            "--height" => self.config.crt.height = positive(arg, value()?)?,
+            "--sprite-radius" => {
+                let radius = value()?;
+                self.config.crt.sprite_radius = radius
+                    .parse()
+                    .map_err(|_| format!("Invalid {arg}: {radius}"))?;
+            }
+            "--samples" => {
+                self.config.schedule = value()?
+                    .parse()
+                    .map_err(|e| format!("Invalid {arg}: {e}"))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn program(&self) -> Result<Vec<Instruction>, String> {
+        let input = input_provider(self.input, self.input_dir)
+            .input(10)
+            .map_err(|e| e.to_string())?;
+        parse_program(&input).map_err(|e| e.to_string())
+    }
+}
+
+fn debug(args: &[String]) -> Result<(), String> {
+    let mut device = DeviceArgs::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !device.parse_option(arg, || option_value(arg, &mut args))? {
+            return Err(format!("Unexpected argument: {arg}"));
+        }
+    }
+    if device.input == Some("-") {
+        return Err("Debugger commands are read from standard input".to_string());
+    }
+    let program = device.program()?;
+    println!("Type help for a list of commands.");
+    Debugger::new(program, device.config)
+        .run(io::stdin().lock(), io::stdout())
+        .map_err(|e| e.to_string())
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Could not write {}: {e}", path.display()))
+}
+
+fn crt(args: &[String]) -> Result<(), String> {
+    let mut device = DeviceArgs::default();
+    let mut options = ImageOptions::default();
+    let (mut ppm, mut png, mut frames) = (None, None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ppm" => ppm = Some(Path::new(option_value(arg, &mut args)?)),
+            "--png" => png = Some(Path::new(option_value(arg, &mut args)?)),
+            "--frames" => frames = Some(Path::new(option_value(arg, &mut args)?)),
+            "--scale" => options.scale = positive(arg, option_value(arg, &mut args)?)?,
+            _ => {
+                if !device.parse_option(arg, || option_value(arg, &mut args))? {
+                    return Err(format!("Unexpected argument: {arg}"));
+                }
+            }
+        }
+    }
+
+    let mut recorder = FrameRecorder::new(CrtRenderer::new(device.config.crt));
+    Cpu::new(device.program()?).run(&mut recorder);
+    let pixels = &recorder.crt.pixels;
+    if let Some(path) = ppm {
+        write_file(path, &to_ppm(pixels, &options))?;
+    }
+    if let Some(path) = png {
+        write_file(path, &to_png(pixels, &options))?;
+    }
+    if let Some(dir) = frames {
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+        for (i, frame) in recorder.frames.iter().enumerate() {
+            write_file(
+                &dir.join(format!("frame{:04}.png", i + 1)),
+                &to_png(frame, &options),
+            )?;
+        }
+    }
+    if ppm.is_none() && png.is_none() && frames.is_none() {
+        print!("{}", to_ansi(pixels, &options));
+    }
+    Ok(())
+}
+
+fn print_round(stats: &RoundStats) {
+    let join = |values: Vec<String>| values.join(" ");
+    println!(
+        "Round {}: inspected {}, holding {}",
+        stats.round,
+        join(stats.inspections.iter().map(u64::to_string).collect()),
+        join(stats.holdings.iter().map(usize::to_string).collect()),
+    );
+}
+
+fn monkeys(args: &[String]) -> Result<(), String> {
+    let (mut input, mut input_dir) = (None, None);
+    let mut backend = Backend::default();
+    let (mut rounds, mut relief, mut top, mut every) = (None, None, None, None::<usize>);
+    let mut fast = false;
+    let mut traces = Vec::new();
+    let mut dot = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input = Some(option_value(arg, &mut args)?),
+            "--input-dir" => input_dir = Some(option_value(arg, &mut args)?),
+            "--worry" => {
+                backend = option_value(arg, &mut args)?
+                    .parse()
+                    .map_err(|e| format!("Invalid {arg}: {e}"))?
+            }
+            "--rounds" => rounds = Some(positive(arg, option_value(arg, &mut args)?)?),
+            "--relief" => {
+                relief = Some(
+                    option_value(arg, &mut args)?
+                        .parse()
+                        .map_err(|e| format!("Invalid {arg}: {e}"))?,
+                )
+            }
+            "--top" => top = Some(positive(arg, option_value(arg, &mut args)?)?),
+            "--every" => every = Some(positive(arg, option_value(arg, &mut args)?)?),
+            "--fast" => fast = true,
+            "--csv" | "--jsonl" => {
+                traces.push((arg.as_str(), Path::new(option_value(arg, &mut args)?)))
+            }
+            "--dot" => dot = Some(Path::new(option_value(arg, &mut args)?)),
+            arg => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+    let input = input_provider(input, input_dir)
+        .input(11)
+        .map_err(|e| e.to_string())?;
+    let day = Day11::parse(&input)
+        .map_err(|e| e.to_string())?
+        .with_backend(backend);
+    if fast && (every.is_some() || !traces.is_empty()) {
+        return Err("--every, --csv and --jsonl can not be combined with --fast".to_string());
+    }
+    if rounds.is_none()
+        && relief.is_none()
+        && top.is_none()
+        && every.is_none()
+        && !fast
+        && traces.is_empty()
+    {
+        if let Some(path) = dot {
+            return write_file(path, to_dot(day.monkeys(), None).as_bytes());
+        }
+        for (part, answer) in [(1, day.part1()), (2, day.part2())] {
+            match answer {
+                Ok(answer) => print_answer(11, part, &answer.to_string()),
+                Err(e) => println!("Day 11 part {part}: {e}"),
+            }
+        }
+        return Ok(());
+    }
+
+    let mut writers = Vec::new();
+    for (format, path) in traces {
+        let file = fs::File::create(path)
+            .map_err(|e| format!("Could not create {}: {e}", path.display()))?;
+        let out = BufWriter::new(file);
+        let writer = match format {
+            "--csv" => TraceWriter::csv(out),
+            _ => Ok(TraceWriter::json_lines(out)),
+        };
+        let writer = writer.map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+        writers.push((writer, path));
+    }
+
+    let (rounds, relief) = (rounds.unwrap_or(20), relief.unwrap_or(Relief::DivideBy(3)));
+    let top = top.unwrap_or(2);
+    let mut write_error = None;
+    let report = if fast {
+        day.fast_forward(rounds, relief)
+    } else {
+        day.simulate(rounds, relief, !writers.is_empty(), |stats| {
+            if every.is_some_and(|every| stats.round % every == 0) {
+                print_round(stats);
+            }
+            for (writer, path) in &mut writers {
+                for throw in &stats.throws {
+                    if let Err(e) = writer.write(throw) {
+                        write_error
+                            .get_or_insert(format!("Could not write {}: {e}", path.display()));
+                    }
+                }
+            }
+        })
+    };
+    let report = report.map_err(|e| e.to_string())?;
+    for (writer, path) in &mut writers {
+        if let Err(e) = writer.flush() {
+            write_error.get_or_insert(format!("Could not write {}: {e}", path.display()));
+        }
+    }
+    if let Some(e) = write_error {
+        return Err(e);
+    }
+    if let Some(path) = dot {
+        write_file(path, to_dot(day.monkeys(), Some(&report)).as_bytes())?;
+    }
+    println!("After {} rounds with relief {relief}:", report.rounds);
+    for (monkey, inspections) in report.top(top) {
+        println!("  Monkey {monkey} inspected {inspections} items");
+    }
+    match report.monkey_business(top) {
+        Some(business) => println!("Monkey business of the top {top}: {business}"),
+        None => println!("Monkey business of the top {top}: overflows or too few monkeys"),
+    }
+    Ok(())
+}
+
+fn climb(args: &[String]) -> Result<(), String> {
+    let (mut input, mut input_dir) = (None, None);
+    let mut part = 1;
+    let mut rule = MoveRule::default();
+    let mut visualize = false;
+    let (mut every, mut delay, mut frames) = (1, 20, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input = Some(option_value(arg, &mut args)?),
+            "--input-dir" => input_dir = Some(option_value(arg, &mut args)?),
+            "--part" => {
+                part = match option_value(arg, &mut args)? {
+                    "1" => 1,
+                    "2" => 2,
+                    p => return Err(format!("Invalid part: {p}")),
+                }
+            }
+            "--max-climb" | "--max-descent" => {
+                let n = option_value(arg, &mut args)?;
+                let n = n.parse().map_err(|_| format!("Invalid {arg}: {n}"))?;
+                match arg.as_str() {
+                    "--max-climb" => rule.max_climb = n,
+                    _ => rule.max_descent = Some(n),
+                }
+            }
+            "--diagonal" => rule.diagonal = true,
+            "--visualize" => visualize = true,
+            "--every" => every = positive(arg, option_value(arg, &mut args)?)?,
+            "--delay" => {
+                let ms = option_value(arg, &mut args)?;
+                delay = ms.parse().map_err(|_| format!("Invalid {arg}: {ms}"))?;
+            }
+            "--frames" => frames = Some(Path::new(option_value(arg, &mut args)?)),
+            "--cost" => {
+                rule.cost = option_value(arg, &mut args)?
+                    .parse()
+                    .map_err(|e| format!("Invalid {arg}: {e}"))?
+            }
+            arg => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+    let input = input_provider(input, input_dir)
+        .input(12)
+        .map_err(|e| e.to_string())?;
+    let day = Day12::parse(&input)
+        .map_err(|e| e.to_string())?
+        .with_rule(rule);
+    if !visualize {
+        let path = match part {
+            1 => day.path(),
+            _ => day.path_from_lowest(),
+        };
+        let path = path.map_err(|e| e.to_string())?;
+        print!("{}", day.render_path(&path));
+        println!("{} steps, costing {}", path.len() - 1, day.cost(&path));
+        return Ok(());
+    }
+
+    if let Some(dir) = frames {
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+    }
+    let mut count = 0;
+    let mut write_error = None;
+    if frames.is_none() {
+        // Clear the screen
+        print!("\x1b[2J");
+    }
+    let path = day.visualize(part == 2, every, |frame| {
+        let drawn = day.render_frame(frame);
+        count += 1;
+        match frames {
+            Some(dir) => {
+                let path = dir.join(format!("frame{count:05}.txt"));
+                if let Err(e) = write_file(&path, drawn.as_bytes()) {
+                    write_error.get_or_insert(e);
+                }
+            }
+            None => {
+                // Draw over the previous frame
+                println!("\x1b[H{drawn}{} squares expanded", frame.expanded);
+                thread::sleep(Duration::from_millis(delay));
+            }
+        }
+    });
+    if let Some(e) = write_error {
+        return Err(e);
+    }
+    let path = path.map_err(|e| e.to_string())?;
+    if let Some(dir) = frames {
+        println!("Wrote {count} frames to {}", dir.display());
+    }
+    println!("{} steps, costing {}", path.len() - 1, day.cost(&path));
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("debug") => debug(&args[1..]),
+        Some("crt") => crt(&args[1..]),
+        Some("monkeys") => monkeys(&args[1..]),
+        Some("climb") => climb(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(message) = result {
+        eprintln!("{message}");
+        process::exit(1);
+    }
+}
@@ -1,48 +0,0 @@
-use std::collections::HashMap;
-use std::fs;
-
-const SAMPLE_INPUT: &str = "
-A Y
-B X
-C Z
-";
-
-fn part1(input: &str) -> u64 {
-    let scores = HashMap::from([
-        ("A X", 1 + 3),
-        ("A Y", 2 + 6),
-        ("A Z", 3 + 0),
-        ("B X", 1 + 0),
-        ("B Y", 2 + 3),
-        ("B Z", 3 + 6),
-        ("C X", 1 + 6),
-        ("C Y", 2 + 0),
-        ("C Z", 3 + 3),
-    ]);
-    input.lines().map(|l| scores[&l]).sum()
-}
-
-fn part2(input: &str) -> u64 {
-    let scores = HashMap::from([
-        ("A X", 3 + 0),
-        ("A Y", 1 + 3),
-        ("A Z", 2 + 6),
-        ("B X", 1 + 0),
-        ("B Y", 2 + 3),
-        ("B Z", 3 + 6),
-        ("C X", 2 + 0),
-        ("C Y", 3 + 3),
-        ("C Z", 1 + 6),
-    ]);
-    input.lines().map(|l| scores[&l]).sum()
-}
-
-fn main() {
-    let full_input = fs::read_to_string("input/day2").unwrap();
-
-    println!("Part 1 (sample input): {}", part1(SAMPLE_INPUT.trim()));
-    println!("Part 1 (full input): {}", part1(full_input.trim()));
-
-    println!("Part 2 (sample input): {}", part2(SAMPLE_INPUT.trim()));
-    println!("Part 2 (full input): {}", part2(full_input.trim()));
-}
@@ -0,0 +1,67 @@
+use crate::{Answer, ParseError, Result, Solution};
+
+pub struct Day1 {
+    calories: Vec<u64>,
+}
+
+impl Solution for Day1 {
+    fn parse(input: &str) -> Result<Day1, ParseError> {
+        let calories = input
+            .trim()
+            .split("\n\n")
+            .map(|s| {
+                s.lines()
+                    .map(|n| {
+                        n.parse::<u64>()
+                            .map_err(|e| ParseError::at(input, n, format!("invalid calories: {e}")))
+                    })
+                    .sum()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Day1 { calories })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(self.calories.iter().copied().max().unwrap_or(0).into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        let mut calories = self.calories.clone();
+        calories.sort_by_key(|&e| -(e as i64));
+        Ok(calories.iter().take(3).sum::<u64>().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day1::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "24000");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day1::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "45000");
+    }
+}
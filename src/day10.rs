@@ -0,0 +1,238 @@
+pub mod config;
+pub mod cpu;
+pub mod debugger;
+pub mod export;
+pub mod ocr;
+
+use crate::{Answer, ParseError, Result, Solution};
+use config::CpuConfig;
+use cpu::{parse_program, Cpu, CrtRenderer, Instruction, SignalSampler};
+
+pub struct Day10 {
+    program: Vec<Instruction>,
+    config: CpuConfig,
+}
+
+impl Day10 {
+    /// Solve for a device with another screen or sampling schedule.
+    pub fn with_config(self, config: CpuConfig) -> Day10 {
+        Day10 { config, ..self }
+    }
+}
+
+impl Solution for Day10 {
+    fn parse(input: &str) -> Result<Day10, ParseError> {
+        Ok(Day10 {
+            program: parse_program(input)?,
+            config: CpuConfig::default(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        let mut sampler = SignalSampler::new(self.config.schedule.clone());
+        Cpu::new(self.program.clone()).run(&mut sampler);
+        Ok(sampler.signal_strengths.into_iter().sum::<i32>().into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        let mut crt = CrtRenderer::new(self.config.crt);
+        Cpu::new(self.program.clone()).run(&mut crt);
+        Ok(ocr::recognise(&crt.pixels)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    const SAMPLE_INPUT: &str = "
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day10::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "13140");
+    }
+
+    #[test]
+    fn part2_sample() {
+        // The sample doesn't draw letters
+        let day = Day10::parse(SAMPLE_INPUT).unwrap();
+        assert!(matches!(day.part2(), Err(Error::NoSolution(_))));
+    }
+
+    #[test]
+    fn part1_custom_schedule() {
+        let config = CpuConfig {
+            schedule: "20+80".parse().unwrap(),
+            ..CpuConfig::default()
+        };
+        let day = Day10::parse(SAMPLE_INPUT).unwrap().with_config(config);
+        // The sample's signal strengths are 420, 1140, 1800, 2940, 2880 and 3960
+        assert_eq!(day.part1().unwrap().to_string(), "5100");
+    }
+
+    #[test]
+    fn crt_sample() {
+        let mut crt = CrtRenderer::default();
+        Cpu::new(parse_program(SAMPLE_INPUT).unwrap()).run(&mut crt);
+        assert_eq!(
+            crt.render(),
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+        );
+    }
+}
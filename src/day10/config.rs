@@ -0,0 +1,112 @@
+//! Settings for devices that differ from the one in the puzzle.
+
+use crate::ParseError;
+use std::str::FromStr;
+
+/// Cycles during which the puzzle samples the signal strength.
+pub const INTERESTING_CYCLE_COUNTS: [u32; 6] = [20, 60, 100, 140, 180, 220];
+
+/// The cycles during which the signal strength is sampled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// Exactly these cycles.
+    Cycles(Vec<u32>),
+    /// Cycle `start` and every `step` cycles after it, forever.
+    Every { start: u32, step: u32 },
+}
+
+impl Schedule {
+    pub fn contains(&self, cycle: u32) -> bool {
+        match *self {
+            Schedule::Cycles(ref cycles) => cycles.contains(&cycle),
+            Schedule::Every { start, step } => {
+                cycle >= start
+                    && (cycle - start)
+                        .checked_rem(step)
+                        .map_or(cycle == start, |r| r == 0)
+            }
+        }
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule::Cycles(INTERESTING_CYCLE_COUNTS.to_vec())
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = ParseError;
+
+    /// Parse a list of cycles like `20,60,100`, or `start+step` like `20+40`.
+    fn from_str(s: &str) -> Result<Schedule, ParseError> {
+        let number = |n: &str| {
+            n.trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| ParseError::at(s, n, "expected a positive number"))
+        };
+        match s.split_once('+') {
+            Some((start, step)) => Ok(Schedule::Every {
+                start: number(start)?,
+                step: number(step)?,
+            }),
+            None => Ok(Schedule::Cycles(
+                s.split(',').map(number).collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrtConfig {
+    pub width: usize,
+    pub height: usize,
+    /// How many pixels the sprite reaches to each side of X.
+    pub sprite_radius: u32,
+}
+
+impl Default for CrtConfig {
+    fn default() -> Self {
+        CrtConfig {
+            width: 40,
+            height: 6,
+            sprite_radius: 1,
+        }
+    }
+}
+
+/// Everything about the device that can be configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuConfig {
+    pub crt: CrtConfig,
+    pub schedule: Schedule,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules() {
+        let every: Schedule = "20+40".parse().unwrap();
+        assert_eq!(
+            every,
+            Schedule::Every {
+                start: 20,
+                step: 40
+            }
+        );
+        assert!(every.contains(20) && every.contains(260));
+        assert!(!every.contains(0) && !every.contains(40));
+
+        let cycles: Schedule = "3, 5".parse().unwrap();
+        assert_eq!(cycles, Schedule::Cycles(vec![3, 5]));
+        assert!(cycles.contains(5) && !cycles.contains(4));
+
+        assert!(Schedule::default().contains(220) && !Schedule::default().contains(260));
+        assert!("20+0".parse::<Schedule>().is_err());
+        assert!("1,,2".parse::<Schedule>().is_err());
+    }
+}
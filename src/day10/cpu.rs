@@ -0,0 +1,284 @@
+//! The handheld device's CPU and the instructions it runs.
+
+use super::config::{CrtConfig, Schedule};
+use crate::grid::{Grid, Pos};
+use crate::ParseError;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    /// Number of cycles the instruction takes to complete.
+    pub fn cycles(self) -> u32 {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+
+    /// Parse `line`, which must be a line of `input`.
+    fn parse(input: &str, line: &str) -> Result<Instruction, ParseError> {
+        let mut words = line.split_whitespace();
+        let opcode = words.next().unwrap_or(line);
+        let operand = words.next();
+        if let Some(extra) = words.next() {
+            return Err(ParseError::at(input, extra, "too many operands"));
+        }
+        match (opcode, operand) {
+            ("noop", None) => Ok(Instruction::Noop),
+            ("noop", Some(v)) => Err(ParseError::at(input, v, "noop takes no operand")),
+            ("addx", Some(v)) => v
+                .parse()
+                .map(Instruction::Addx)
+                .map_err(|e| ParseError::at(input, v, format!("invalid addx value: {e}"))),
+            ("addx", None) => Err(ParseError::at(input, line, "addx needs a value")),
+            _ => Err(ParseError::at(
+                input,
+                opcode,
+                format!("unknown opcode `{opcode}`"),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::Addx(v) => write!(f, "addx {v}"),
+        }
+    }
+}
+
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| Instruction::parse(input, line))
+        .collect()
+}
+
+/// Something that watches the CPU run.
+pub trait Observer {
+    /// Called during every cycle, where `cycle` counts from 1 and `x` is the
+    /// value of the X register during the cycle.
+    fn during_cycle(&mut self, cycle: u32, x: i32);
+}
+
+impl<A: Observer, B: Observer> Observer for (A, B) {
+    fn during_cycle(&mut self, cycle: u32, x: i32) {
+        self.0.during_cycle(cycle, x);
+        self.1.during_cycle(cycle, x);
+    }
+}
+
+impl<O: Observer + ?Sized> Observer for &mut O {
+    fn during_cycle(&mut self, cycle: u32, x: i32) {
+        (**self).during_cycle(cycle, x);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cpu {
+    program: Vec<Instruction>,
+    /// Index of the instruction being executed.
+    pc: usize,
+    /// Cycles the current instruction has been executing for.
+    progress: u32,
+    x: i32,
+    /// Number of completed cycles.
+    cycle: u32,
+}
+
+impl Cpu {
+    pub fn new(program: Vec<Instruction>) -> Cpu {
+        Cpu {
+            program,
+            pc: 0,
+            progress: 0,
+            x: 1,
+            cycle: 0,
+        }
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// Number of completed cycles.
+    pub fn cycle(&self) -> u32 {
+        self.cycle
+    }
+
+    /// Index in the program of the instruction being executed.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Whether the next cycle starts a new instruction.
+    pub fn at_instruction_start(&self) -> bool {
+        self.progress == 0
+    }
+
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    pub fn halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Run one cycle. Returns false without doing anything if the program
+    /// has finished.
+    pub fn step<O: Observer + ?Sized>(&mut self, observer: &mut O) -> bool {
+        let Some(&instruction) = self.program.get(self.pc) else {
+            return false;
+        };
+        self.cycle += 1;
+        observer.during_cycle(self.cycle, self.x);
+        self.progress += 1;
+        if self.progress == instruction.cycles() {
+            match instruction {
+                Instruction::Noop => {}
+                Instruction::Addx(v) => self.x += v,
+            }
+            self.pc += 1;
+            self.progress = 0;
+        }
+        true
+    }
+
+    /// Run until the program has finished.
+    pub fn run<O: Observer + ?Sized>(&mut self, observer: &mut O) {
+        while self.step(observer) {}
+    }
+}
+
+/// Records the signal strength, the cycle number times X, during the cycles
+/// in its schedule.
+#[derive(Debug, Default)]
+pub struct SignalSampler {
+    schedule: Schedule,
+    pub signal_strengths: Vec<i32>,
+}
+
+impl SignalSampler {
+    pub fn new(schedule: Schedule) -> SignalSampler {
+        SignalSampler {
+            schedule,
+            signal_strengths: Vec::new(),
+        }
+    }
+}
+
+impl Observer for SignalSampler {
+    fn during_cycle(&mut self, cycle: u32, x: i32) {
+        if self.schedule.contains(cycle) {
+            self.signal_strengths.push(x * cycle as i32);
+        }
+    }
+}
+
+/// Draws one pixel per cycle, row by row, which is lit if the sprite
+/// centered on X covers it.
+#[derive(Debug)]
+pub struct CrtRenderer {
+    sprite_radius: u32,
+    pub pixels: Grid<bool>,
+}
+
+impl CrtRenderer {
+    pub fn new(config: CrtConfig) -> CrtRenderer {
+        CrtRenderer {
+            sprite_radius: config.sprite_radius,
+            pixels: Grid::filled(false, config.width, config.height),
+        }
+    }
+
+    /// Whether the sprite covers column `column` when the register is `x`.
+    pub fn sprite_covers(&self, column: usize, x: i32) -> bool {
+        (column as i64 - x as i64).unsigned_abs() <= self.sprite_radius as u64
+    }
+
+    /// The screen with `#` for lit and `.` for dark pixels.
+    pub fn render(&self) -> String {
+        self.pixels.render(|_, &lit| if lit { '#' } else { '.' })
+    }
+}
+
+impl Default for CrtRenderer {
+    fn default() -> Self {
+        CrtRenderer::new(CrtConfig::default())
+    }
+}
+
+impl Observer for CrtRenderer {
+    fn during_cycle(&mut self, cycle: u32, x: i32) {
+        let width = self.pixels.width();
+        let index = (cycle - 1) as usize;
+        let pos = Pos(index % width, index / width);
+        let lit = self.sprite_covers(pos.0, x);
+        if let Some(pixel) = self.pixels.get_mut(pos) {
+            *pixel = lit;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Trace(Vec<(u32, i32)>);
+
+    impl Observer for Trace {
+        fn during_cycle(&mut self, cycle: u32, x: i32) {
+            self.0.push((cycle, x));
+        }
+    }
+
+    #[test]
+    fn small_program() {
+        let program = parse_program("noop\naddx 3\naddx -5\n").unwrap();
+        let mut cpu = Cpu::new(program);
+        let mut trace = Trace::default();
+        cpu.run(&mut trace);
+        assert_eq!(trace.0, [(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!((cpu.cycle(), cpu.x()), (5, -1));
+        assert!(cpu.halted());
+        assert!(!cpu.step(&mut trace));
+    }
+
+    #[test]
+    fn configured_observers() {
+        let program = parse_program("addx 2\naddx 3\nnoop\naddx -4\nnoop\n").unwrap();
+        let crt = CrtRenderer::new(CrtConfig {
+            width: 4,
+            height: 2,
+            sprite_radius: 0,
+        });
+        let sampler = SignalSampler::new(Schedule::Every { start: 1, step: 3 });
+        let mut observers = (crt, sampler);
+        Cpu::new(program).run(&mut observers);
+        let (crt, sampler) = observers;
+        assert_eq!(crt.render(), ".#.#\n....\n");
+        assert_eq!(sampler.signal_strengths, [1, 12, 42]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = parse_program("noop\nmulx 3\n").unwrap_err();
+        assert_eq!(e.message, "unknown opcode `mulx`");
+        assert_eq!(e.location.unwrap().line, 2);
+        let e = parse_program("addx\n").unwrap_err();
+        assert_eq!(e.message, "addx needs a value");
+        let e = parse_program("addx 1 2\n").unwrap_err();
+        assert_eq!(e.message, "too many operands");
+        let e = parse_program("noop\naddx x\n").unwrap_err();
+        assert_eq!(e.location.unwrap().column, 6);
+    }
+}
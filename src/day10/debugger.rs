@@ -0,0 +1,318 @@
+//! An interactive debugger for the CPU, driven by one command per line.
+
+use super::config::{CpuConfig, Schedule};
+use super::cpu::{Cpu, CrtRenderer, Instruction};
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  step [n], s [n]        run n cycles (default 1)
+  continue, c            run until a breakpoint or the end of the program
+  sample                 run until the next cycle that samples the signal
+  break cycle <n>        stop before cycle n
+  break line <n>         stop before the instruction on line n starts
+  delete <cycle|line> <n>
+  breakpoints            list breakpoints
+  watch                  toggle printing X whenever it changes
+  print, p               show the CPU state
+  crt                    show the CRT row being drawn and the sprite
+  help, h                show this help
+  quit, q                exit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Breakpoint {
+    /// Before cycle `n` runs.
+    Cycle(u32),
+    /// Before the instruction on line `n` starts, counting from 1.
+    Line(usize),
+}
+
+pub struct Debugger {
+    cpu: Cpu,
+    breakpoints: BTreeSet<Breakpoint>,
+    /// Print X whenever it changes.
+    watch: bool,
+    schedule: Schedule,
+    crt: CrtRenderer,
+}
+
+impl Debugger {
+    pub fn new(program: Vec<Instruction>, config: CpuConfig) -> Debugger {
+        Debugger {
+            cpu: Cpu::new(program),
+            breakpoints: BTreeSet::new(),
+            watch: false,
+            schedule: config.schedule,
+            crt: CrtRenderer::new(config.crt),
+        }
+    }
+
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    /// The breakpoint the CPU is stopped at, if any.
+    fn hit_breakpoint(&self) -> Option<Breakpoint> {
+        let cycle = Breakpoint::Cycle(self.cpu.cycle() + 1);
+        let line = Breakpoint::Line(self.cpu.pc() + 1);
+        if self.breakpoints.contains(&cycle) {
+            Some(cycle)
+        } else if self.cpu.at_instruction_start() && self.breakpoints.contains(&line) {
+            Some(line)
+        } else {
+            None
+        }
+    }
+
+    /// Run cycles until `stop` returns true after a cycle, or the program
+    /// ends. Returns the number of cycles run.
+    fn run_until(
+        &mut self,
+        output: &mut impl Write,
+        mut stop: impl FnMut(&Self) -> bool,
+    ) -> io::Result<u32> {
+        let mut cycles = 0;
+        loop {
+            let x = self.cpu.x();
+            if !self.cpu.step(&mut self.crt) {
+                return Ok(cycles);
+            }
+            cycles += 1;
+            if self.watch && self.cpu.x() != x {
+                writeln!(
+                    output,
+                    "x changed from {x} to {} after cycle {}",
+                    self.cpu.x(),
+                    self.cpu.cycle()
+                )?;
+            }
+            if stop(self) {
+                return Ok(cycles);
+            }
+        }
+    }
+
+    fn print_state(&self, output: &mut impl Write) -> io::Result<()> {
+        if self.cpu.halted() {
+            return writeln!(
+                output,
+                "program finished after cycle {}, x = {}",
+                self.cpu.cycle(),
+                self.cpu.x()
+            );
+        }
+        let pc = self.cpu.pc();
+        writeln!(
+            output,
+            "next cycle {}, line {}: {}{}, x = {}",
+            self.cpu.cycle() + 1,
+            pc + 1,
+            self.cpu.program()[pc],
+            if self.cpu.at_instruction_start() {
+                ""
+            } else {
+                " (in progress)"
+            },
+            self.cpu.x()
+        )
+    }
+
+    /// The row of the CRT being drawn so far, and the sprite's position below
+    /// it.
+    fn print_crt(&self, output: &mut impl Write) -> io::Result<()> {
+        let pixels = &self.crt.pixels;
+        let width = pixels.width() as u32;
+        let y = (self.cpu.cycle() / width) as usize % pixels.height();
+        let drawn = (self.cpu.cycle() % width) as usize;
+        let row: String = pixels
+            .row(y)
+            .take(drawn)
+            .map(|pos| if pixels[pos] { '#' } else { '.' })
+            .collect();
+        let x = self.cpu.x();
+        let sprite: String = (0..pixels.width())
+            .map(|i| {
+                if self.crt.sprite_covers(i, x) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        writeln!(output, "row {}:  {row}", y + 1)?;
+        writeln!(output, "sprite: {sprite}")?;
+        writeln!(output, "        {:>1$}", "^", drawn + 1)
+    }
+
+    fn stopped(&self, output: &mut impl Write) -> io::Result<()> {
+        if let Some(breakpoint) = self.hit_breakpoint() {
+            writeln!(output, "hit breakpoint {}", describe(breakpoint))?;
+        }
+        self.print_state(output)
+    }
+
+    /// Run one command. Returns false if the debugger should exit.
+    pub fn command(&mut self, line: &str, output: &mut impl Write) -> io::Result<bool> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["step" | "s"] => {
+                self.run_until(output, |_| true)?;
+                self.stopped(output)?;
+            }
+            ["step" | "s", n] => match n.parse::<u32>() {
+                Ok(n) if n > 0 => {
+                    let mut remaining = n;
+                    self.run_until(output, |_| {
+                        remaining -= 1;
+                        remaining == 0
+                    })?;
+                    self.stopped(output)?;
+                }
+                _ => writeln!(output, "invalid number of cycles: {n}")?,
+            },
+            ["continue" | "c"] => {
+                self.run_until(output, |debugger| debugger.hit_breakpoint().is_some())?;
+                self.stopped(output)?;
+            }
+            ["sample"] => {
+                self.run_until(output, |debugger| {
+                    debugger.schedule.contains(debugger.cpu.cycle() + 1)
+                })?;
+                let cycle = self.cpu.cycle() + 1;
+                if self.schedule.contains(cycle) {
+                    writeln!(
+                        output,
+                        "signal strength during cycle {cycle}: {}",
+                        cycle as i32 * self.cpu.x()
+                    )?;
+                } else {
+                    writeln!(output, "no more samples")?;
+                }
+                self.print_state(output)?;
+            }
+            ["break" | "delete", kind, n] => {
+                let breakpoint = match (*kind, n.parse()) {
+                    ("cycle", Ok(n)) if n > 0 => Breakpoint::Cycle(n as u32),
+                    ("line", Ok(n)) if n > 0 && n <= self.cpu.program().len() => {
+                        Breakpoint::Line(n)
+                    }
+                    _ => {
+                        writeln!(output, "invalid breakpoint: {kind} {n}")?;
+                        return Ok(true);
+                    }
+                };
+                if words[0] == "break" {
+                    self.breakpoints.insert(breakpoint);
+                    writeln!(output, "added breakpoint {}", describe(breakpoint))?;
+                } else if self.breakpoints.remove(&breakpoint) {
+                    writeln!(output, "deleted breakpoint {}", describe(breakpoint))?;
+                } else {
+                    writeln!(output, "no breakpoint {}", describe(breakpoint))?;
+                }
+            }
+            ["breakpoints"] => {
+                if self.breakpoints.is_empty() {
+                    writeln!(output, "no breakpoints")?;
+                }
+                for &breakpoint in &self.breakpoints {
+                    writeln!(output, "{}", describe(breakpoint))?;
+                }
+            }
+            ["watch"] => {
+                self.watch = !self.watch;
+                let state = if self.watch { "on" } else { "off" };
+                writeln!(output, "watching x {state}")?;
+            }
+            ["print" | "p"] => self.print_state(output)?,
+            ["crt"] => self.print_crt(output)?,
+            ["help" | "h"] => writeln!(output, "{HELP}")?,
+            ["quit" | "q"] => return Ok(false),
+            _ => writeln!(output, "unknown command: {line} (try help)")?,
+        }
+        Ok(true)
+    }
+
+    /// Read commands from `input` until it ends or the `quit` command.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        self.print_state(&mut output)?;
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            if !self.command(&line?, &mut output)? {
+                break;
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(())
+    }
+}
+
+fn describe(breakpoint: Breakpoint) -> String {
+    match breakpoint {
+        Breakpoint::Cycle(n) => format!("at cycle {n}"),
+        Breakpoint::Line(n) => format!("at line {n}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day10::cpu::parse_program;
+
+    fn run(program: &str, commands: &str) -> String {
+        let mut debugger = Debugger::new(parse_program(program).unwrap(), CpuConfig::default());
+        let mut output = Vec::new();
+        debugger.run(commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn step_and_breakpoints() {
+        let output = run(
+            "noop\naddx 3\naddx -5\nnoop\n",
+            "step\nbreak line 3\nbreak cycle 6\ncontinue\ncontinue\nstep 10\n",
+        );
+        assert_eq!(
+            output,
+            "\
+next cycle 1, line 1: noop, x = 1
+> next cycle 2, line 2: addx 3, x = 1
+> added breakpoint at line 3
+> added breakpoint at cycle 6
+> hit breakpoint at line 3
+next cycle 4, line 3: addx -5, x = 4
+> hit breakpoint at cycle 6
+next cycle 6, line 4: noop, x = -1
+> program finished after cycle 6, x = -1
+> "
+        );
+    }
+
+    #[test]
+    fn watch_and_crt() {
+        let output = run("addx 3\nnoop\naddx -2\n", "watch\nstep 4\ncrt\nq\nstep\n");
+        assert_eq!(
+            output,
+            "\
+next cycle 1, line 1: addx 3, x = 1
+> watching x on
+> x changed from 1 to 4 after cycle 2
+next cycle 5, line 3: addx -2 (in progress), x = 4
+> row 1:  ##.#
+sprite: ...###..................................
+            ^
+> "
+        );
+    }
+
+    #[test]
+    fn sample() {
+        let program = "noop\n".repeat(25);
+        let output = run(&program, "sample\nsample\n");
+        assert!(output.contains("signal strength during cycle 20: 20\nnext cycle 20"));
+        assert!(output.contains("no more samples\nprogram finished after cycle 25"));
+    }
+}
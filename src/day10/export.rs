@@ -0,0 +1,237 @@
+//! Writing the CRT's pixels as images and terminal art.
+
+use super::cpu::{CrtRenderer, Observer};
+use crate::grid::{Grid, Pos};
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageOptions {
+    /// Width and height in image pixels of each CRT pixel.
+    pub scale: usize,
+    pub lit: Rgb,
+    pub dark: Rgb,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        ImageOptions {
+            scale: 10,
+            lit: [255, 176, 0],
+            dark: [24, 16, 8],
+        }
+    }
+}
+
+/// The image's colour values, row by row, along with its width and height.
+fn rgb_rows(pixels: &Grid<bool>, options: &ImageOptions) -> (usize, usize, Vec<Vec<u8>>) {
+    let scale = options.scale.max(1);
+    let (width, height) = (pixels.width() * scale, pixels.height() * scale);
+    let rows = (0..height)
+        .map(|y| {
+            (0..width)
+                .flat_map(|x| {
+                    if pixels[Pos(x / scale, y / scale)] {
+                        options.lit
+                    } else {
+                        options.dark
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    (width, height, rows)
+}
+
+/// A binary PPM (P6) image.
+pub fn to_ppm(pixels: &Grid<bool>, options: &ImageOptions) -> Vec<u8> {
+    let (width, height, rows) = rgb_rows(pixels, options);
+    let mut ppm = format!("P6\n{width} {height}\n255\n").into_bytes();
+    ppm.extend(rows.into_iter().flatten());
+    ppm
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// `data` as a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut res = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        res.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        res.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        res.extend(len.to_le_bytes());
+        res.extend((!len).to_le_bytes());
+        res.extend(block);
+    }
+    res.extend(adler32(data).to_be_bytes());
+    res
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// An 8 bit RGB PNG image. The image data isn't compressed, which is fine
+/// for pictures this small.
+pub fn to_png(pixels: &Grid<bool>, options: &ImageOptions) -> Vec<u8> {
+    let (width, height, rows) = rgb_rows(pixels, options);
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // Bit depth 8, colour type RGB, default compression, filter and no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+    // Each row starts with its filter type, which is none
+    let data: Vec<u8> = rows
+        .into_iter()
+        .flat_map(|row| std::iter::once(0).chain(row))
+        .collect();
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_chunk(&mut png, b"IHDR", &header);
+    png_chunk(&mut png, b"IDAT", &zlib_stored(&data));
+    png_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// The screen as coloured blocks for a terminal supporting 24-bit colour,
+/// two characters wide per pixel so it isn't stretched.
+pub fn to_ansi(pixels: &Grid<bool>, options: &ImageOptions) -> String {
+    let mut res = String::new();
+    for y in 0..pixels.height() {
+        for pos in pixels.row(y) {
+            let [r, g, b] = if pixels[pos] {
+                options.lit
+            } else {
+                options.dark
+            };
+            res.push_str(&format!("\x1b[38;2;{r};{g};{b}m██"));
+        }
+        res.push_str("\x1b[0m\n");
+    }
+    res
+}
+
+/// Records the screen after every cycle, for an animation of the beam
+/// drawing the picture.
+#[derive(Debug, Default)]
+pub struct FrameRecorder {
+    pub crt: CrtRenderer,
+    pub frames: Vec<Grid<bool>>,
+}
+
+impl FrameRecorder {
+    pub fn new(crt: CrtRenderer) -> FrameRecorder {
+        FrameRecorder {
+            crt,
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl Observer for FrameRecorder {
+    fn during_cycle(&mut self, cycle: u32, x: i32) {
+        self.crt.during_cycle(cycle, x);
+        self.frames.push(self.crt.pixels.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day10::cpu::{parse_program, Cpu};
+
+    fn pixels() -> Grid<bool> {
+        Grid::new(vec![true, false], 2, 1)
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn ppm() {
+        let options = ImageOptions {
+            scale: 1,
+            lit: [1, 2, 3],
+            dark: [4, 5, 6],
+        };
+        assert_eq!(
+            to_ppm(&pixels(), &options),
+            b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06"
+        );
+        let options = ImageOptions {
+            scale: 3,
+            ..options
+        };
+        assert_eq!(to_ppm(&pixels(), &options).len(), 11 + 6 * 3 * 3);
+    }
+
+    #[test]
+    fn png() {
+        let options = ImageOptions {
+            scale: 2,
+            ..ImageOptions::default()
+        };
+        let png = to_png(&pixels(), &options);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x04\0\0\0\x02\x08\x02"));
+        // IEND chunk with its well known CRC
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+        // 2 rows of a filter byte and 4 pixels, in one stored block
+        let data_len = 2 * (1 + 4 * 3);
+        assert_eq!(png.len(), 8 + 25 + (12 + 2 + 5 + data_len + 4) + 12);
+    }
+
+    #[test]
+    fn ansi() {
+        let options = ImageOptions::default();
+        assert_eq!(
+            to_ansi(&pixels(), &options),
+            "\x1b[38;2;255;176;0m██\x1b[38;2;24;16;8m██\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let mut recorder = FrameRecorder::default();
+        Cpu::new(parse_program("noop\naddx 5\n").unwrap()).run(&mut recorder);
+        assert_eq!(recorder.frames.len(), 3);
+        let lit = |frame: &Grid<bool>| frame.values().filter(|&&lit| lit).count();
+        assert_eq!(
+            recorder.frames.iter().map(lit).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Reading the capital letters the CRT draws, which are 4x6 pixel glyphs with
+//! a blank column between them.
+
+use crate::grid::{Grid, Pos};
+use crate::Error;
+use std::fmt;
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+
+/// Every glyph known to be used, row by row.
+const GLYPHS: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnrecognisedGlyph {
+    /// Position of the glyph in the text, counting from 0.
+    pub index: usize,
+    /// The glyph's pixels, drawn with `#` and `.`.
+    pub cell: String,
+}
+
+impl fmt::Display for UnrecognisedGlyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unrecognised glyph at letter {}:\n{}",
+            self.index + 1,
+            self.cell
+        )
+    }
+}
+
+impl std::error::Error for UnrecognisedGlyph {}
+
+impl From<UnrecognisedGlyph> for Error {
+    fn from(e: UnrecognisedGlyph) -> Error {
+        Error::no_solution(e.to_string())
+    }
+}
+
+/// The pixels of the glyph at `index` as `#` and `.`, row by row.
+fn glyph_pixels(pixels: &Grid<bool>, index: usize) -> String {
+    let left = index * (GLYPH_WIDTH + 1);
+    (0..GLYPH_HEIGHT)
+        .flat_map(|y| (left..left + GLYPH_WIDTH).map(move |x| Pos(x, y)))
+        .map(|pos| match pixels.get(pos) {
+            Some(true) => '#',
+            _ => '.',
+        })
+        .collect()
+}
+
+/// Read the letters drawn on a screen six pixels high.
+pub fn recognise(pixels: &Grid<bool>) -> Result<String, UnrecognisedGlyph> {
+    let letters = (pixels.width() + 1) / (GLYPH_WIDTH + 1);
+    (0..letters)
+        .map(|index| {
+            let glyph = glyph_pixels(pixels, index);
+            GLYPHS
+                .iter()
+                .find(|(_, known)| *known == glyph)
+                .map(|&(letter, _)| letter)
+                .ok_or_else(|| {
+                    let rows = glyph.as_bytes().chunks(GLYPH_WIDTH);
+                    UnrecognisedGlyph {
+                        index,
+                        cell: rows
+                            .map(|row| String::from_utf8_lossy(row) + "\n")
+                            .collect(),
+                    }
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(picture: &str) -> Grid<bool> {
+        Grid::parse(picture, |_, c| Ok(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn letters() {
+        let pixels = screen(
+            "
+####.#....###..#....####..##..####.#....
+#....#....#..#.#.......#.#..#....#.#....
+###..#....#..#.#......#..#......#..#....
+#....#....###..#.....#...#.##..#...#....
+#....#....#....#....#....#..#.#....#....
+####.####.#....####.####..###.####.####.
+",
+        );
+        assert_eq!(recognise(&pixels).unwrap(), "ELPLZGZL");
+    }
+
+    #[test]
+    fn unrecognised() {
+        let pixels = screen(
+            "
+.##..#..#
+#..#.#..#
+#..#.#..#
+####.#..#
+#..#.##.#
+#..#.#..#
+",
+        );
+        assert_eq!(
+            recognise(&pixels),
+            Err(UnrecognisedGlyph {
+                index: 1,
+                cell: "#..#\n#..#\n#..#\n#..#\n##.#\n#..#\n".to_string()
+            })
+        );
+    }
+}
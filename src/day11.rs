@@ -0,0 +1,348 @@
+pub mod cycles;
+pub mod dot;
+pub mod expr;
+pub mod simulation;
+pub mod trace;
+pub mod worry;
+
+use crate::error::parse_all;
+use crate::{Answer, Error, ParseError, Result, Solution};
+use expr::{operation, Expr};
+use nom::{
+    bytes::complete::tag,
+    character::complete,
+    character::complete::newline,
+    combinator::cut,
+    multi::{count, separated_list1},
+    IResult,
+};
+use num_bigint::BigUint;
+use simulation::{MonkeySimulation, Relief, Report, RoundStats};
+use worry::{Backend, Residues, Worry};
+
+/// A monkey as described in the input.
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<u64>,
+    operation: Expr,
+    test: u64,
+    true_target: usize,
+    false_target: usize,
+}
+
+impl Monkey {
+    /// Who the monkey throws an item to, depending on whether it passed the test.
+    fn target(&self, passed: bool) -> usize {
+        if passed {
+            self.true_target
+        } else {
+            self.false_target
+        }
+    }
+}
+
+fn items(input: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(tag(", "), complete::u64)(input)
+}
+
+/// Where a monkey's numbers are in the input, for reporting problems with them.
+struct Spans<'a> {
+    id: &'a str,
+    test: &'a str,
+    targets: [&'a str; 2],
+}
+
+fn monkey(input: &str) -> IResult<&str, (u32, Monkey, Spans<'_>)> {
+    let (input, _) = tag("Monkey ")(input)?;
+    // Past this point the input can't be anything else than a monkey, so
+    // report errors where they are instead of backtracking
+    cut(monkey_body)(input)
+}
+
+fn monkey_body(input: &str) -> IResult<&str, (u32, Monkey, Spans<'_>)> {
+    let id_span = input;
+    let (input, id) = complete::u32(input)?;
+    let (input, _) = tag(":\n  Starting items: ")(input)?;
+    let (input, items) = items(input)?;
+    let (input, _) = tag("\n  Operation: ")(input)?;
+    let (input, operation) = operation(input)?;
+    let (input, _) = tag("\n  Test: divisible by ")(input)?;
+    let test_span = input;
+    let (input, test) = complete::u64(input)?;
+    let (input, _) = tag("\n    If true: throw to monkey ")(input)?;
+    let true_span = input;
+    let (input, true_target) = complete::u32(input)?;
+    let (input, _) = tag("\n    If false: throw to monkey ")(input)?;
+    let false_span = input;
+    let (input, false_target) = complete::u32(input)?;
+    let monkey = Monkey {
+        items,
+        operation,
+        test,
+        true_target: true_target as usize,
+        false_target: false_target as usize,
+    };
+    let spans = Spans {
+        id: id_span,
+        test: test_span,
+        targets: [true_span, false_span],
+    };
+    Ok((input, (id, monkey, spans)))
+}
+
+fn parse_monkeys(input: &str) -> IResult<&str, Vec<(u32, Monkey, Spans<'_>)>> {
+    separated_list1(count(newline, 2), monkey)(input)
+}
+
+/// Check that the monkeys are numbered in order from 0, that they only
+/// throw to other monkeys that exist and that their tests don't divide by
+/// zero, reporting every problem.
+fn validate(input: &str, monkeys: &[(u32, Monkey, Spans)]) -> Result<(), ParseError> {
+    let mut problems = Vec::new();
+    for (i, (id, monkey, spans)) in monkeys.iter().enumerate() {
+        if *id as usize != i {
+            problems.push(ParseError::at(
+                input,
+                spans.id,
+                format!("expected monkey {i}, found monkey {id}"),
+            ));
+        }
+        if monkey.test == 0 {
+            problems.push(ParseError::at(
+                input,
+                spans.test,
+                "can't test divisibility by zero",
+            ));
+        }
+        for (target, span) in [monkey.true_target, monkey.false_target]
+            .into_iter()
+            .zip(spans.targets)
+        {
+            if target >= monkeys.len() {
+                problems.push(ParseError::at(
+                    input,
+                    span,
+                    format!("monkey {target} doesn't exist"),
+                ));
+            } else if target == i {
+                problems.push(ParseError::at(
+                    input,
+                    span,
+                    format!("monkey {i} throws to itself"),
+                ));
+            }
+        }
+    }
+    ParseError::combine(problems)
+}
+
+pub struct Day11 {
+    monkeys: Vec<Monkey>,
+    backend: Backend,
+}
+
+impl Day11 {
+    /// Solve using another number type for worry levels.
+    pub fn with_backend(self, backend: Backend) -> Day11 {
+        Day11 { backend, ..self }
+    }
+
+    pub fn monkeys(&self) -> &[Monkey] {
+        &self.monkeys
+    }
+
+    /// Play `rounds` rounds with the chosen backend, calling `on_round` after
+    /// each of them. With `trace`, the statistics include every throw.
+    pub fn simulate(
+        &self,
+        rounds: usize,
+        relief: Relief,
+        trace: bool,
+        on_round: impl FnMut(&RoundStats),
+    ) -> Result<Report> {
+        fn simulate<W: Worry>(
+            monkeys: &[Monkey],
+            rounds: usize,
+            relief: Relief,
+            trace: bool,
+            mut on_round: impl FnMut(&RoundStats),
+        ) -> Result<Report> {
+            let mut sim = MonkeySimulation::<W>::new(monkeys, rounds, relief)?;
+            if trace {
+                sim = sim.with_trace();
+            }
+            for stats in sim.by_ref() {
+                on_round(&stats?);
+            }
+            Ok(sim.report())
+        }
+        match self.backend {
+            Backend::U64 => simulate::<u64>(&self.monkeys, rounds, relief, trace, on_round),
+            Backend::U128 => simulate::<u128>(&self.monkeys, rounds, relief, trace, on_round),
+            Backend::Residues => {
+                simulate::<Residues>(&self.monkeys, rounds, relief, trace, on_round)
+            }
+            Backend::BigInt => simulate::<BigUint>(&self.monkeys, rounds, relief, trace, on_round),
+        }
+    }
+
+    /// The report after `rounds` rounds with the chosen backend, without
+    /// playing every round, see `cycles`.
+    pub fn fast_forward(&self, rounds: usize, relief: Relief) -> Result<Report> {
+        match self.backend {
+            Backend::U64 => cycles::fast_forward::<u64>(&self.monkeys, rounds, relief),
+            Backend::U128 => cycles::fast_forward::<u128>(&self.monkeys, rounds, relief),
+            Backend::Residues => cycles::fast_forward::<Residues>(&self.monkeys, rounds, relief),
+            Backend::BigInt => cycles::fast_forward::<BigUint>(&self.monkeys, rounds, relief),
+        }
+    }
+
+    fn monkey_business(&self, rounds: usize, relief: Relief) -> Result<Answer> {
+        let report = self.simulate(rounds, relief, false, |_| {})?;
+        let business = report
+            .monkey_business(2)
+            .and_then(|business| u64::try_from(business).ok())
+            .ok_or_else(|| Error::no_solution("monkey business overflows"))?;
+        Ok(business.into())
+    }
+}
+
+impl Solution for Day11 {
+    fn parse(input: &str) -> Result<Day11, ParseError> {
+        let monkeys = parse_all(input, input.trim(), parse_monkeys)?;
+        validate(input, &monkeys)?;
+        let monkeys = monkeys.into_iter().map(|(_, monkey, _)| monkey).collect();
+        Ok(Day11 {
+            monkeys,
+            backend: Backend::default(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        self.monkey_business(20, Relief::DivideBy(3))
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        self.monkey_business(10_000, Relief::TestsProduct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) const SAMPLE_INPUT: &str = "
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "10605");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "2713310158");
+    }
+
+    #[test]
+    fn backends() {
+        for backend in [
+            Backend::U64,
+            Backend::U128,
+            Backend::Residues,
+            Backend::BigInt,
+        ] {
+            let day = Day11::parse(SAMPLE_INPUT).unwrap().with_backend(backend);
+            assert_eq!(day.part2().unwrap().to_string(), "2713310158");
+        }
+        for backend in [Backend::U64, Backend::U128, Backend::BigInt] {
+            let day = Day11::parse(SAMPLE_INPUT).unwrap().with_backend(backend);
+            assert_eq!(day.part1().unwrap().to_string(), "10605");
+        }
+        let day = Day11::parse(SAMPLE_INPUT)
+            .unwrap()
+            .with_backend(Backend::Residues);
+        assert!(day.part1().is_err());
+    }
+
+    #[test]
+    fn overflow() {
+        // Without relief, squaring worry levels overflows a u64 in a few rounds
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(
+            day.simulate(20, Relief::None, false, |_| {})
+                .unwrap_err()
+                .to_string(),
+            "no solution: worry level overflowed"
+        );
+        let day = day.with_backend(Backend::BigInt);
+        assert!(day.simulate(20, Relief::None, false, |_| {}).is_ok());
+    }
+
+    #[test]
+    fn validation() {
+        let input = SAMPLE_INPUT
+            .replace("Monkey 1:", "Monkey 2:")
+            .replace("divisible by 13", "divisible by 0")
+            .replace("If false: throw to monkey 3", "If false: throw to monkey 4")
+            .replace("If true: throw to monkey 0", "If true: throw to monkey 3");
+        let e = Day11::parse(&input).err().unwrap();
+        let problems: Vec<_> = std::iter::once(&e).chain(&e.more).collect();
+        let lines: Vec<_> = problems
+            .iter()
+            .map(|p| p.location.as_ref().unwrap().line)
+            .collect();
+        assert_eq!(lines, [7, 9, 19, 21, 27]);
+        assert_eq!(problems[0].message, "monkey 4 doesn't exist");
+        assert_eq!(problems[1].message, "expected monkey 1, found monkey 2");
+        assert_eq!(problems[2].message, "can't test divisibility by zero");
+        assert_eq!(problems[4].message, "monkey 3 throws to itself");
+        assert_eq!(e.to_string().matches("parse error at line").count(), 5);
+    }
+
+    #[test]
+    fn other_operations() {
+        let input = SAMPLE_INPUT
+            .replace("old * 19", "(old + 2) * 7")
+            .replace("old * old", "old * old + 3");
+        let day = Day11::parse(&input).unwrap();
+        assert!(day.part1().is_ok());
+        assert!(day.part2().is_ok());
+
+        let day = Day11::parse(&SAMPLE_INPUT.replace("old + 6", "old / 2 + 6")).unwrap();
+        assert!(day.part1().is_ok());
+        assert!(matches!(day.part2(), Err(Error::NoSolution(_))));
+
+        let e = Day11::parse(&SAMPLE_INPUT.replace("old + 6", "old ^ 2")).err();
+        assert_eq!(e.unwrap().location.unwrap().line, 11);
+    }
+}
@@ -0,0 +1,152 @@
+//! Fast-forwarding long games of keep away.
+//!
+//! Items don't affect each other: where an item goes only depends on which
+//! monkey holds it and its worry level. When worry levels are kept small, an
+//! item's state at the start of a round eventually repeats, and from then on
+//! its inspections repeat with it.
+
+use super::simulation::{inspect, Relief, Report};
+use super::worry::Worry;
+use super::Monkey;
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The same report as `MonkeySimulation` after `rounds` rounds, found by
+/// following each item on its own until its state repeats. Only a remainder
+/// as relief guarantees that it does, so other reliefs are rejected.
+pub fn fast_forward<W: Worry + Eq + Hash>(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: Relief,
+) -> Result<Report> {
+    let relief = relief.resolve(monkeys)?;
+    if !matches!(relief, Relief::Modulo(_)) {
+        return Err(Error::no_solution(format!(
+            "worry levels may never repeat with relief {relief}, so they can't be fast-forwarded"
+        )));
+    }
+    let overflow = || Error::no_solution("inspection count overflows");
+    // Throws to the true and the false target of each monkey, as in a report
+    let width = 2 * monkeys.len();
+    let divisors: Vec<u64> = monkeys.iter().map(|m| m.test).collect();
+    let mut throws = vec![0u64; width];
+    // Round at which each state was seen, and throws before each round, width
+    // numbers per round
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    for (start, monkey) in monkeys.iter().enumerate() {
+        for &worry in &monkey.items {
+            seen.clear();
+            history.clear();
+            history.resize(width, 0);
+            let (mut holder, mut worry) = (start, W::new(worry, &divisors));
+            let mut round = 0;
+            let at = |history: &[u64], round: usize| history[round * width..][..width].to_vec();
+            let totals = loop {
+                if round == rounds {
+                    break at(&history, round);
+                }
+                if let Some(&first) = seen.get(&(holder, worry.clone())) {
+                    let period = round - first;
+                    let (cycles, rest) = ((rounds - round) / period, (rounds - round) % period);
+                    let (first, now, part) = (
+                        at(&history, first),
+                        at(&history, round),
+                        at(&history, first + rest),
+                    );
+                    break (0..width)
+                        .map(|i| {
+                            (now[i] - first[i])
+                                .checked_mul(cycles as u64)
+                                .and_then(|n| n.checked_add(now[i] + part[i] - first[i]))
+                                .ok_or_else(overflow)
+                        })
+                        .collect::<Result<_>>()?;
+                }
+                seen.insert((holder, worry.clone()), round);
+
+                let mut counts = at(&history, round);
+                // Thrown to a later monkey, the item is inspected again in
+                // the same round
+                loop {
+                    let (new, passed) = inspect(monkeys, holder, &worry, relief)?;
+                    counts[2 * holder + usize::from(!passed)] += 1;
+                    let target = monkeys[holder].target(passed);
+                    let later = target > holder;
+                    (worry, holder) = (new, target);
+                    if !later {
+                        break;
+                    }
+                }
+                history.extend(counts);
+                round += 1;
+            };
+            for (total, count) in throws.iter_mut().zip(totals) {
+                *total = total.checked_add(count).ok_or_else(overflow)?;
+            }
+        }
+    }
+    let throw_counts: Vec<[u64; 2]> = throws.chunks(2).map(|c| [c[0], c[1]]).collect();
+    Ok(Report {
+        rounds,
+        inspections: throw_counts
+            .iter()
+            .map(|[t, f]| t.checked_add(*f).ok_or_else(overflow))
+            .collect::<Result<_>>()?,
+        throw_counts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::simulation::MonkeySimulation;
+    use crate::day11::{tests::SAMPLE_INPUT, Day11};
+    use crate::Solution;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn same_as_simulation() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        let monkeys = day.monkeys();
+        for (rounds, relief) in [
+            (0, Relief::TestsProduct),
+            (1, Relief::TestsProduct),
+            (1000, Relief::TestsProduct),
+            (1000, Relief::Modulo(1_000_000)),
+            (10_000, Relief::TestsProduct),
+        ] {
+            let expected = MonkeySimulation::<u64>::new(monkeys, rounds, relief)
+                .unwrap()
+                .run()
+                .unwrap();
+            assert_eq!(
+                fast_forward::<u64>(monkeys, rounds, relief).unwrap(),
+                expected,
+                "{rounds} rounds with relief {relief}"
+            );
+        }
+    }
+
+    #[test]
+    fn huge_round_counts() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        let report = fast_forward::<u64>(day.monkeys(), 1_000_000_000_000, Relief::TestsProduct);
+        let report = report.unwrap();
+        assert_eq!(report.rounds, 1_000_000_000_000);
+        assert!(report.monkey_business(2).unwrap() > 10u128.pow(24));
+
+        // Without a remainder, worry levels can grow forever
+        for relief in [Relief::None, Relief::DivideBy(3)] {
+            let e = fast_forward::<BigUint>(day.monkeys(), 1_000_000_000_000, relief);
+            assert!(matches!(e, Err(Error::NoSolution(_))));
+        }
+
+        let e = fast_forward::<u64>(day.monkeys(), usize::MAX, Relief::TestsProduct);
+        assert_eq!(
+            e.unwrap_err().to_string(),
+            "no solution: inspection count overflows"
+        );
+    }
+}
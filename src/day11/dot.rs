@@ -0,0 +1,71 @@
+//! The monkeys and who they throw to, as a Graphviz graph.
+
+use super::simulation::Report;
+use super::Monkey;
+
+/// A DOT graph with a node per monkey and an edge to each of its targets.
+/// With a `report`, edges are labelled with how many items were thrown along
+/// them, and drawn thicker the more there were.
+pub fn to_dot(monkeys: &[Monkey], report: Option<&Report>) -> String {
+    let mut dot = String::from("digraph monkeys {\n    node [shape=box];\n");
+    for (i, monkey) in monkeys.iter().enumerate() {
+        dot.push_str(&format!(
+            "    m{i} [label=\"Monkey {i}\\nnew = {}\\ndivisible by {}\"];\n",
+            monkey.operation, monkey.test
+        ));
+    }
+    let most = report.map_or(0, |r| {
+        r.throw_counts.iter().flatten().copied().max().unwrap_or(0)
+    });
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for (branch, passed, colour) in [(0, true, "darkgreen"), (1, false, "red")] {
+            let target = monkey.target(passed);
+            let mut attributes = format!("color={colour}");
+            match report {
+                Some(report) => {
+                    let count = report.throw_counts[i][branch];
+                    let width = 1.0 + 4.0 * count as f64 / most.max(1) as f64;
+                    attributes.push_str(&format!(
+                        ", label=\"{passed}: {count}\", penwidth={width:.1}"
+                    ));
+                }
+                None => attributes.push_str(&format!(", label=\"{passed}\"")),
+            }
+            dot.push_str(&format!("    m{i} -> m{target} [{attributes}];\n"));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::simulation::Relief;
+    use crate::day11::{tests::SAMPLE_INPUT, Day11};
+    use crate::Solution;
+
+    #[test]
+    fn graph() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        let dot = to_dot(day.monkeys(), None);
+        assert!(dot.starts_with("digraph monkeys {\n"));
+        assert!(dot.contains("    m2 [label=\"Monkey 2\\nnew = old * old\\ndivisible by 13\"];\n"));
+        assert!(dot.contains("    m0 -> m2 [color=darkgreen, label=\"true\"];\n"));
+        assert!(dot.contains("    m0 -> m3 [color=red, label=\"false\"];\n"));
+        assert_eq!(dot.matches("->").count(), 8);
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn weighted_graph() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        let report = day
+            .simulate(20, Relief::DivideBy(3), false, |_| {})
+            .unwrap();
+        let dot = to_dot(day.monkeys(), Some(&report));
+        assert!(dot.contains("    m3 -> m1 [color=red, label=\"false: 92\", penwidth=4.6];\n"));
+        assert!(dot.contains("    m0 -> m2 [color=darkgreen, label=\"true: 0\", penwidth=1.0];\n"));
+        assert!(dot.contains("    m0 -> m3 [color=red, label=\"false: 101\", penwidth=5.0];\n"));
+    }
+}
@@ -0,0 +1,214 @@
+//! The expressions monkeys use to compute new worry levels, like
+//! `old * old + 3` or `(old + 2) * 7`.
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, one_of, space0},
+    combinator::{map, value},
+    multi::fold_many0,
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinOp {
+    fn symbol(self) -> char {
+        match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+            BinOp::Rem => '%',
+        }
+    }
+
+    fn from_symbol(c: char) -> BinOp {
+        match c {
+            '+' => BinOp::Add,
+            '-' => BinOp::Sub,
+            '*' => BinOp::Mul,
+            '/' => BinOp::Div,
+            '%' => BinOp::Rem,
+            _ => unreachable!("only called with operator symbols"),
+        }
+    }
+
+    /// Operators binding tighter have a higher precedence.
+    fn precedence(self) -> u8 {
+        match self {
+            BinOp::Add | BinOp::Sub => 1,
+            BinOp::Mul | BinOp::Div | BinOp::Rem => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// The old worry level.
+    Old,
+    Literal(u64),
+    Binary(Box<Expr>, BinOp, Box<Expr>),
+}
+
+impl Expr {
+    fn binary(left: Expr, op: BinOp, right: Expr) -> Expr {
+        Expr::Binary(Box::new(left), op, Box::new(right))
+    }
+
+    /// Whether `op` appears anywhere in the expression.
+    pub fn uses(&self, op: BinOp) -> bool {
+        match self {
+            Expr::Old | Expr::Literal(_) => false,
+            Expr::Binary(left, o, right) => *o == op || left.uses(op) || right.uses(op),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Formats with only the parentheses that are needed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Old => write!(f, "old"),
+            Expr::Literal(n) => write!(f, "{n}"),
+            Expr::Binary(left, op, right) => {
+                let needs_parens = |e: &Expr, is_right: bool| match e {
+                    Expr::Binary(_, inner, _) => {
+                        inner.precedence() < op.precedence()
+                            // Operators are left-associative, so a right hand side
+                            // of the same precedence needs them, unless both
+                            // operators are the same associative one
+                            || (is_right
+                                && inner.precedence() == op.precedence()
+                                && !(inner == op && matches!(op, BinOp::Add | BinOp::Mul)))
+                    }
+                    _ => false,
+                };
+                let side = |f: &mut fmt::Formatter<'_>, e: &Expr, is_right| {
+                    if needs_parens(e, is_right) {
+                        write!(f, "({e})")
+                    } else {
+                        write!(f, "{e}")
+                    }
+                };
+                side(f, left, false)?;
+                write!(f, " {} ", op.symbol())?;
+                side(f, right, true)
+            }
+        }
+    }
+}
+
+fn atom(input: &str) -> IResult<&str, Expr> {
+    alt((
+        value(Expr::Old, tag("old")),
+        map(complete::u64, Expr::Literal),
+        delimited(pair(tag("("), space0), expression, pair(space0, tag(")"))),
+    ))(input)
+}
+
+/// Left-associative chain of `operand`s joined by any of `operators`.
+fn chain<'a>(
+    operators: &'static str,
+    operand: fn(&'a str) -> IResult<&'a str, Expr>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Expr> {
+    move |input| {
+        let (input, first) = operand(input)?;
+        fold_many0(
+            pair(delimited(space0, one_of(operators), space0), operand),
+            move || first.clone(),
+            |left, (op, right)| Expr::binary(left, BinOp::from_symbol(op), right),
+        )(input)
+    }
+}
+
+fn term(input: &str) -> IResult<&str, Expr> {
+    chain("*/%", atom)(input)
+}
+
+/// An expression of `old`, numbers, `+ - * / %` and parentheses, with the
+/// usual precedence.
+pub fn expression(input: &str) -> IResult<&str, Expr> {
+    chain("+-", term)(input)
+}
+
+/// The right hand side of an operation like `new = old * 19`.
+pub fn operation(input: &str) -> IResult<&str, Expr> {
+    preceded(tag("new = "), expression)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::worry::{Worry, WorryError};
+
+    fn parse(input: &str) -> Expr {
+        let (rest, expr) = expression(input).unwrap();
+        assert_eq!(rest, "");
+        expr
+    }
+
+    #[test]
+    fn precedence_and_parentheses() {
+        assert_eq!(4u64.eval(&parse("old * old + 3")), Ok(19));
+        assert_eq!(4u64.eval(&parse("(old + 2) * 7")), Ok(42));
+        assert_eq!(4u64.eval(&parse("3 + old * old")), Ok(19));
+        assert_eq!(10u64.eval(&parse("old - 2 - 1")), Ok(7));
+        assert_eq!(2u64.eval(&parse("100 / old / 5")), Ok(10));
+        assert_eq!(10u64.eval(&parse("old % 7 * ( old+1 )")), Ok(33));
+    }
+
+    #[test]
+    fn eval_errors() {
+        assert_eq!(4u64.eval(&parse("old - 5")), Err(WorryError::Negative));
+        assert_eq!(
+            4u64.eval(&parse("old / (old - 4)")),
+            Err(WorryError::DivisionByZero)
+        );
+        assert_eq!(
+            u64::MAX.eval(&parse("old * old")),
+            Err(WorryError::Overflow)
+        );
+    }
+
+    #[test]
+    fn uses() {
+        assert!(parse("old * (old % 3)").uses(BinOp::Rem));
+        assert!(!parse("old * (old % 3)").uses(BinOp::Div));
+    }
+
+    #[test]
+    fn display() {
+        for expr in [
+            "old * old + 3",
+            "(old + 2) * 7",
+            "old - (old - 1)",
+            "old - 1 - 2",
+            "old / (2 * 3)",
+            "old * 2 * 3",
+            "old * (2 / 3)",
+            "old + 2 + 3",
+        ] {
+            assert_eq!(parse(expr).to_string(), expr);
+        }
+        assert_eq!(parse("((old))+(2 * 3)").to_string(), "old + 2 * 3");
+    }
+
+    #[test]
+    fn operations() {
+        assert_eq!(
+            operation("new = old * 19"),
+            Ok(("", Expr::binary(Expr::Old, BinOp::Mul, Expr::Literal(19))))
+        );
+        assert!(operation("new = old ^ 2").unwrap().0 == " ^ 2");
+    }
+}
@@ -0,0 +1,359 @@
+//! Playing keep away round by round, with statistics along the way.
+
+use super::expr::BinOp;
+use super::trace::Throw;
+use super::worry::Worry;
+use super::Monkey;
+use crate::{Error, ParseError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// How worry levels are lowered after each inspection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relief {
+    /// Worry levels are never lowered.
+    None,
+    /// Divided by a number, rounding down, like in part 1.
+    DivideBy(u64),
+    /// The remainder modulo a number.
+    Modulo(u64),
+    /// The remainder modulo the product of the monkeys' tests, which doesn't
+    /// change where items are thrown, like in part 2.
+    TestsProduct,
+}
+
+impl fmt::Display for Relief {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Relief::None => write!(f, "none"),
+            Relief::DivideBy(d) => write!(f, "/{d}"),
+            Relief::Modulo(m) => write!(f, "%{m}"),
+            Relief::TestsProduct => write!(f, "product"),
+        }
+    }
+}
+
+impl FromStr for Relief {
+    type Err = ParseError;
+
+    /// Parse `none`, `/<n>`, `%<n>` or `product`.
+    fn from_str(s: &str) -> Result<Relief, ParseError> {
+        let number = |n: &str| {
+            n.parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| ParseError::at(s, n, "expected a positive number"))
+        };
+        match s {
+            "none" => Ok(Relief::None),
+            "product" => Ok(Relief::TestsProduct),
+            _ if s.starts_with('/') => Ok(Relief::DivideBy(number(&s[1..])?)),
+            _ if s.starts_with('%') => Ok(Relief::Modulo(number(&s[1..])?)),
+            _ => Err(ParseError::new("expected none, /<n>, %<n> or product")),
+        }
+    }
+}
+
+impl Relief {
+    /// Replace `TestsProduct` by the remainder modulo the product. Remainders
+    /// only follow the true worry levels through additions and
+    /// multiplications, so operations that subtract or divide are rejected
+    /// with them. Whether a remainder changes where items are thrown is up to
+    /// its modulus: a multiple of every test doesn't.
+    pub(super) fn resolve(self, monkeys: &[Monkey]) -> Result<Relief> {
+        let relief = match self {
+            Relief::TestsProduct => Relief::Modulo(
+                monkeys
+                    .iter()
+                    .try_fold(1u64, |product, m| product.checked_mul(m.test))
+                    .ok_or_else(|| Error::no_solution("product of the tests overflows"))?,
+            ),
+            relief => relief,
+        };
+        if let Relief::Modulo(_) = relief {
+            for (i, monkey) in monkeys.iter().enumerate() {
+                let uses = |ops: &[BinOp]| ops.iter().any(|&op| monkey.operation.uses(op));
+                let what = if uses(&[BinOp::Div, BinOp::Rem]) {
+                    "divides"
+                } else if uses(&[BinOp::Sub]) {
+                    "subtracts"
+                } else {
+                    continue;
+                };
+                return Err(Error::no_solution(format!(
+                    "monkey {i}'s operation {what}, so worry levels can't be kept small"
+                )));
+            }
+        }
+        Ok(relief)
+    }
+}
+
+/// Monkey number `i` inspecting an item, with a resolved `relief`. Returns the
+/// new worry level and whether it passes the monkey's test.
+pub(super) fn inspect<W: Worry>(
+    monkeys: &[Monkey],
+    i: usize,
+    item: &W,
+    relief: Relief,
+) -> Result<(W, bool)> {
+    let monkey = &monkeys[i];
+    let item = item.eval(&monkey.operation)?;
+    let item = match relief {
+        Relief::None => item,
+        Relief::DivideBy(d) => item.divided_by(d)?,
+        Relief::Modulo(m) => item.modulo(m)?,
+        Relief::TestsProduct => unreachable!("relief is resolved"),
+    };
+    let passed = item.divisible(i, monkey.test);
+    Ok((item, passed))
+}
+
+/// What happened during one round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundStats {
+    /// Starting from 1.
+    pub round: usize,
+    /// How many items each monkey inspected this round.
+    pub inspections: Vec<u64>,
+    /// How many items each monkey holds after the round.
+    pub holdings: Vec<usize>,
+    /// Every throw, in order, if the simulation is traced.
+    pub throws: Vec<Throw>,
+}
+
+/// An item with its worry level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item<W> {
+    /// The item's position among all starting items in the input, from 0.
+    pub id: usize,
+    pub worry: W,
+}
+
+/// Inspection counts after some rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub rounds: usize,
+    /// How many items each monkey inspected in total.
+    pub inspections: Vec<u64>,
+    /// How many items each monkey threw to its true and its false target.
+    pub throw_counts: Vec<[u64; 2]>,
+}
+
+impl Report {
+    /// The `k` monkeys that inspected the most items, as pairs of monkey and
+    /// inspection count, busiest first.
+    pub fn top(&self, k: usize) -> Vec<(usize, u64)> {
+        let mut monkeys: Vec<_> = self.inspections.iter().copied().enumerate().collect();
+        monkeys.sort_by(|(i, a), (j, b)| b.cmp(a).then(i.cmp(j)));
+        monkeys.truncate(k);
+        monkeys
+    }
+
+    /// The product of the `k` highest inspection counts, or None if there are
+    /// fewer than `k` monkeys or the product overflows.
+    pub fn monkey_business(&self, k: usize) -> Option<u128> {
+        if k > self.inspections.len() {
+            return None;
+        }
+        self.top(k)
+            .iter()
+            .try_fold(1u128, |product, &(_, n)| product.checked_mul(n.into()))
+    }
+}
+
+/// Monkeys playing keep away for a number of rounds, with worry levels of
+/// type `W`. As an iterator, it plays the remaining rounds one at a time.
+pub struct MonkeySimulation<'a, W> {
+    monkeys: &'a [Monkey],
+    rounds: usize,
+    relief: Relief,
+    round: usize,
+    items: Vec<Vec<Item<W>>>,
+    inspections: Vec<u64>,
+    throw_counts: Vec<[u64; 2]>,
+    trace: bool,
+}
+
+impl<'a, W: Worry> MonkeySimulation<'a, W> {
+    pub fn new(monkeys: &'a [Monkey], rounds: usize, relief: Relief) -> Result<Self> {
+        let relief = relief.resolve(monkeys)?;
+        let divisors: Vec<u64> = monkeys.iter().map(|m| m.test).collect();
+        let mut ids = 0..;
+        let items = monkeys
+            .iter()
+            .map(|m| {
+                m.items
+                    .iter()
+                    .zip(ids.by_ref())
+                    .map(|(&n, id)| Item {
+                        id,
+                        worry: W::new(n, &divisors),
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(MonkeySimulation {
+            monkeys,
+            rounds,
+            relief,
+            round: 0,
+            items,
+            inspections: vec![0; monkeys.len()],
+            throw_counts: vec![[0; 2]; monkeys.len()],
+            trace: false,
+        })
+    }
+
+    /// Record every throw in the rounds' statistics.
+    pub fn with_trace(self) -> Self {
+        MonkeySimulation {
+            trace: true,
+            ..self
+        }
+    }
+
+    /// How many rounds have been played.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// The items each monkey holds.
+    pub fn holdings(&self) -> &[Vec<Item<W>>] {
+        &self.items
+    }
+
+    pub fn report(&self) -> Report {
+        Report {
+            rounds: self.round,
+            inspections: self.inspections.clone(),
+            throw_counts: self.throw_counts.clone(),
+        }
+    }
+
+    /// Play all remaining rounds.
+    pub fn run(&mut self) -> Result<Report> {
+        for stats in self.by_ref() {
+            stats?;
+        }
+        Ok(self.report())
+    }
+
+    fn play_round(&mut self) -> Result<RoundStats> {
+        let mut inspections = vec![0; self.monkeys.len()];
+        let mut throws = Vec::new();
+        for (i, count) in inspections.iter_mut().enumerate() {
+            for item in std::mem::take(&mut self.items[i]) {
+                *count += 1;
+                let (worry, passed) = inspect(self.monkeys, i, &item.worry, self.relief)?;
+                self.throw_counts[i][usize::from(!passed)] += 1;
+                let target = self.monkeys[i].target(passed);
+                if self.trace {
+                    throws.push(Throw {
+                        round: self.round + 1,
+                        monkey: i,
+                        item: item.id,
+                        worry_before: item.worry.to_string(),
+                        worry_after: worry.to_string(),
+                        target,
+                    });
+                }
+                self.items[target].push(Item { worry, ..item });
+            }
+        }
+        for (total, n) in self.inspections.iter_mut().zip(&inspections) {
+            *total += n;
+        }
+        self.round += 1;
+        Ok(RoundStats {
+            round: self.round,
+            inspections,
+            holdings: self.items.iter().map(Vec::len).collect(),
+            throws,
+        })
+    }
+}
+
+impl<W: Worry> Iterator for MonkeySimulation<'_, W> {
+    type Item = Result<RoundStats>;
+
+    /// Play the next round. After an error, no more rounds are played.
+    fn next(&mut self) -> Option<Result<RoundStats>> {
+        if self.round >= self.rounds {
+            return None;
+        }
+        let stats = self.play_round();
+        if stats.is_err() {
+            self.rounds = self.round;
+        }
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::{tests::SAMPLE_INPUT, Day11};
+    use crate::Solution;
+
+    #[test]
+    fn rounds() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        let mut sim = MonkeySimulation::<u64>::new(&day.monkeys, 20, Relief::DivideBy(3)).unwrap();
+        let first = sim.next().unwrap().unwrap();
+        assert_eq!(first.round, 1);
+        assert_eq!(first.inspections, [2, 4, 3, 5]);
+        assert_eq!(first.holdings, [4, 6, 0, 0]);
+        let holdings = &sim.holdings()[0];
+        assert_eq!(
+            holdings.iter().map(|i| i.worry).collect::<Vec<_>>(),
+            [20, 23, 27, 26]
+        );
+        assert_eq!(
+            holdings.iter().map(|i| i.id).collect::<Vec<_>>(),
+            [2, 3, 4, 5]
+        );
+
+        let report = sim.run().unwrap();
+        assert_eq!(report.rounds, 20);
+        assert_eq!(report.inspections, [101, 95, 7, 105]);
+        assert_eq!(report.throw_counts, [[0, 101], [4, 91], [4, 3], [13, 92]]);
+        assert_eq!(report.top(2), [(3, 105), (0, 101)]);
+        assert_eq!(report.monkey_business(2), Some(10605));
+        assert_eq!(report.monkey_business(3), Some(10605 * 95));
+        assert_eq!(report.monkey_business(5), None);
+        assert!(sim.next().is_none());
+    }
+
+    #[test]
+    fn relief() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        let report = MonkeySimulation::<u64>::new(&day.monkeys, 1000, Relief::TestsProduct)
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(report.inspections, [5204, 4792, 199, 5192]);
+        let throws: Vec<_> = report.throw_counts.iter().map(|[t, f]| t + f).collect();
+        assert_eq!(throws, report.inspections);
+
+        let mut sim = MonkeySimulation::<u64>::new(&day.monkeys, 20, Relief::None).unwrap();
+        assert!(sim.run().is_err());
+        assert!(sim.next().is_none());
+
+        assert_eq!("/3".parse(), Ok(Relief::DivideBy(3)));
+        assert_eq!("%96577".parse(), Ok(Relief::Modulo(96577)));
+        assert_eq!("product".parse(), Ok(Relief::TestsProduct));
+        assert!("/0".parse::<Relief>().is_err());
+
+        // A remainder of a large worry level can be smaller than what is
+        // subtracted from it
+        let day = Day11::parse(&SAMPLE_INPUT.replace("old + 6", "old * 1000 - 7")).unwrap();
+        assert!(MonkeySimulation::<u64>::new(&day.monkeys, 20, Relief::DivideBy(3)).is_ok());
+        let e = MonkeySimulation::<u64>::new(&day.monkeys, 20, Relief::TestsProduct);
+        assert_eq!(
+            e.err().unwrap().to_string(),
+            "no solution: monkey 1's operation subtracts, so worry levels can't be kept small"
+        );
+        assert!("*2".parse::<Relief>().is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Every throw during a game, for analysing it with other tools.
+
+use std::io::{self, Write};
+
+/// A monkey inspecting an item and throwing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Throw {
+    pub round: usize,
+    pub monkey: usize,
+    /// The item's position among all starting items in the input, from 0.
+    pub item: usize,
+    /// Worry levels as formatted by the backend.
+    pub worry_before: String,
+    pub worry_after: String,
+    pub target: usize,
+}
+
+pub const CSV_HEADER: &str = "round,monkey,item,worry_before,worry_after,target";
+
+impl Throw {
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.round, self.monkey, self.item, self.worry_before, self.worry_after, self.target
+        )
+    }
+
+    /// A JSON object, with worry levels as numbers when they are integers and
+    /// as strings otherwise.
+    pub fn to_json(&self) -> String {
+        let worry = |w: &str| {
+            if !w.is_empty() && w.bytes().all(|b| b.is_ascii_digit()) {
+                w.to_string()
+            } else {
+                format!("\"{}\"", w.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+        };
+        format!(
+            r#"{{"round":{},"monkey":{},"item":{},"worry_before":{},"worry_after":{},"target":{}}}"#,
+            self.round,
+            self.monkey,
+            self.item,
+            worry(&self.worry_before),
+            worry(&self.worry_after),
+            self.target
+        )
+    }
+}
+
+/// Writes throws as they happen, in either format.
+pub enum TraceWriter<W> {
+    Csv(W),
+    JsonLines(W),
+}
+
+impl<W: Write> TraceWriter<W> {
+    /// Starts with the header line for CSV.
+    pub fn csv(mut out: W) -> io::Result<TraceWriter<W>> {
+        writeln!(out, "{CSV_HEADER}")?;
+        Ok(TraceWriter::Csv(out))
+    }
+
+    pub fn json_lines(out: W) -> TraceWriter<W> {
+        TraceWriter::JsonLines(out)
+    }
+
+    pub fn write(&mut self, throw: &Throw) -> io::Result<()> {
+        match self {
+            TraceWriter::Csv(out) => writeln!(out, "{}", throw.to_csv()),
+            TraceWriter::JsonLines(out) => writeln!(out, "{}", throw.to_json()),
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        match self {
+            TraceWriter::Csv(out) | TraceWriter::JsonLines(out) => out.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::{simulation::Relief, worry::Backend};
+    use crate::day11::{tests::SAMPLE_INPUT, Day11};
+    use crate::Solution;
+
+    fn throw(worry_after: &str) -> Throw {
+        Throw {
+            round: 1,
+            monkey: 0,
+            item: 1,
+            worry_before: "98".to_string(),
+            worry_after: worry_after.to_string(),
+            target: 3,
+        }
+    }
+
+    #[test]
+    fn formats() {
+        let mut csv = TraceWriter::csv(Vec::new()).unwrap();
+        csv.write(&throw("620")).unwrap();
+        let TraceWriter::Csv(csv) = csv else {
+            unreachable!()
+        };
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "round,monkey,item,worry_before,worry_after,target\n1,0,1,98,620,3\n"
+        );
+        assert_eq!(
+            throw("(2 1 7)").to_json(),
+            r#"{"round":1,"monkey":0,"item":1,"worry_before":98,"worry_after":"(2 1 7)","target":3}"#
+        );
+    }
+
+    #[test]
+    fn sample_trace() {
+        let day = Day11::parse(SAMPLE_INPUT).unwrap();
+        let mut throws = Vec::new();
+        day.simulate(2, Relief::DivideBy(3), true, |stats| {
+            throws.extend(stats.throws.iter().cloned())
+        })
+        .unwrap();
+        assert_eq!(throws[1], throw("620"));
+        // Monkey 3's own item is inspected after the ones thrown to it
+        let first_round: Vec<_> = throws.iter().filter(|t| t.round == 1).collect();
+        assert_eq!(first_round.len(), 14);
+        assert_eq!(first_round[9].item, 9);
+        assert_eq!(first_round[10].item, 0);
+
+        let day = day.with_backend(Backend::Residues);
+        let mut throws = Vec::new();
+        day.simulate(1, Relief::TestsProduct, true, |stats| {
+            throws.extend(stats.throws.iter().cloned())
+        })
+        .unwrap();
+        assert_eq!(throws[0].worry_before, "(10 3 1 11)");
+
+        let mut untraced = 0;
+        day.simulate(1, Relief::TestsProduct, false, |stats| {
+            untraced += stats.throws.len()
+        })
+        .unwrap();
+        assert_eq!(untraced, 0);
+    }
+}
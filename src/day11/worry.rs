@@ -0,0 +1,329 @@
+//! Number types for worry levels, which grow quickly without relief.
+
+use super::expr::{BinOp, Expr};
+use crate::{Error, ParseError};
+use num_bigint::BigUint;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorryError {
+    /// The worry level doesn't fit in the number type.
+    Overflow,
+    /// The worry level would become negative.
+    Negative,
+    DivisionByZero,
+    /// The number type can't do this operation.
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for WorryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorryError::Overflow => write!(f, "worry level overflowed"),
+            WorryError::Negative => write!(f, "worry level became negative"),
+            WorryError::DivisionByZero => write!(f, "worry level divided by zero"),
+            WorryError::Unsupported(what) => write!(f, "{what}"),
+        }
+    }
+}
+
+impl std::error::Error for WorryError {}
+
+impl From<WorryError> for Error {
+    fn from(e: WorryError) -> Error {
+        Error::no_solution(e.to_string())
+    }
+}
+
+/// A worry level. `Display` is used for traces.
+pub trait Worry: Clone + fmt::Display {
+    /// `n`, for monkeys testing divisibility by `divisors`.
+    fn new(n: u64, divisors: &[u64]) -> Self;
+
+    /// `n`, for the same monkeys as `self`.
+    fn constant(&self, n: u64) -> Self;
+
+    fn apply(&self, op: BinOp, other: &Self) -> Result<Self, WorryError>;
+
+    /// Whether this is divisible by `divisor`, which is the test of monkey
+    /// number `monkey`.
+    fn divisible(&self, monkey: usize, divisor: u64) -> bool;
+
+    fn divided_by(&self, d: u64) -> Result<Self, WorryError> {
+        self.apply(BinOp::Div, &self.constant(d))
+    }
+
+    fn modulo(&self, m: u64) -> Result<Self, WorryError> {
+        self.apply(BinOp::Rem, &self.constant(m))
+    }
+
+    /// Evaluate `expr` with this as the old worry level.
+    fn eval(&self, expr: &Expr) -> Result<Self, WorryError> {
+        match expr {
+            Expr::Old => Ok(self.clone()),
+            Expr::Literal(n) => Ok(self.constant(*n)),
+            Expr::Binary(left, op, right) => self.eval(left)?.apply(*op, &self.eval(right)?),
+        }
+    }
+}
+
+fn checked<T>(res: Option<T>, op: BinOp, divisor_is_zero: bool) -> Result<T, WorryError> {
+    res.ok_or(match op {
+        BinOp::Div | BinOp::Rem if divisor_is_zero => WorryError::DivisionByZero,
+        BinOp::Sub => WorryError::Negative,
+        _ => WorryError::Overflow,
+    })
+}
+
+macro_rules! checked_worry {
+    ($t:ty) => {
+        impl Worry for $t {
+            fn new(n: u64, _divisors: &[u64]) -> Self {
+                n.into()
+            }
+
+            fn constant(&self, n: u64) -> Self {
+                n.into()
+            }
+
+            fn apply(&self, op: BinOp, other: &Self) -> Result<Self, WorryError> {
+                let res = match op {
+                    BinOp::Add => self.checked_add(*other),
+                    BinOp::Sub => self.checked_sub(*other),
+                    BinOp::Mul => self.checked_mul(*other),
+                    BinOp::Div => self.checked_div(*other),
+                    BinOp::Rem => self.checked_rem(*other),
+                };
+                checked(res, op, *other == 0)
+            }
+
+            fn divisible(&self, _monkey: usize, divisor: u64) -> bool {
+                self % <$t>::from(divisor) == 0
+            }
+        }
+    };
+}
+
+checked_worry!(u64);
+checked_worry!(u128);
+
+impl Worry for BigUint {
+    fn new(n: u64, _divisors: &[u64]) -> Self {
+        n.into()
+    }
+
+    fn constant(&self, n: u64) -> Self {
+        n.into()
+    }
+
+    fn apply(&self, op: BinOp, other: &Self) -> Result<Self, WorryError> {
+        let zero = BigUint::default();
+        match op {
+            BinOp::Add => Ok(self + other),
+            BinOp::Sub if self < other => Err(WorryError::Negative),
+            BinOp::Sub => Ok(self - other),
+            BinOp::Mul => Ok(self * other),
+            BinOp::Div | BinOp::Rem if *other == zero => Err(WorryError::DivisionByZero),
+            BinOp::Div => Ok(self / other),
+            BinOp::Rem => Ok(self % other),
+        }
+    }
+
+    fn divisible(&self, _monkey: usize, divisor: u64) -> bool {
+        self % divisor == BigUint::default()
+    }
+}
+
+/// A worry level stored as its remainders modulo each monkey's divisor,
+/// which is all that is needed to test it. Adding, subtracting and
+/// multiplying work on the remainders, but dividing doesn't, and a
+/// subtraction can't tell whether the result would be negative.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Residues {
+    moduli: Rc<[u64]>,
+    residues: Vec<u64>,
+    /// The exact number, for constants.
+    constant: Option<u64>,
+}
+
+impl Residues {
+    fn from_moduli(n: u64, moduli: Rc<[u64]>) -> Residues {
+        let residues = moduli.iter().map(|m| n % m).collect();
+        Residues {
+            moduli,
+            residues,
+            constant: None,
+        }
+    }
+
+    /// Only taking the remainder modulo a constant multiple of every modulus
+    /// can be done, and changes nothing. Other divisors could be zero, which
+    /// the remainders can't tell.
+    fn divide(&self, op: BinOp, other: &Self) -> Result<Self, WorryError> {
+        let multiple_of_all = other.residues.iter().all(|&r| r == 0);
+        match (op, other.constant) {
+            (_, Some(0)) => Err(WorryError::DivisionByZero),
+            (BinOp::Rem, Some(_)) if multiple_of_all => Ok(self.clone()),
+            _ => Err(WorryError::Unsupported(
+                "worry levels kept as residues can't be divided",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Residues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, residue) in self.residues.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{residue}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Worry for Residues {
+    fn new(n: u64, divisors: &[u64]) -> Self {
+        Residues::from_moduli(n, divisors.into())
+    }
+
+    fn constant(&self, n: u64) -> Self {
+        Residues {
+            constant: Some(n),
+            ..Residues::from_moduli(n, self.moduli.clone())
+        }
+    }
+
+    fn apply(&self, op: BinOp, other: &Self) -> Result<Self, WorryError> {
+        let apply = |a: u64, b: u64, m: u64| {
+            let (a, b, m) = (a as u128, b as u128, m as u128);
+            let res = match op {
+                BinOp::Add => (a + b) % m,
+                BinOp::Sub => (a + m - b) % m,
+                BinOp::Mul => a * b % m,
+                BinOp::Div | BinOp::Rem => unreachable!("checked below"),
+            };
+            res as u64
+        };
+        match op {
+            BinOp::Div | BinOp::Rem => self.divide(op, other),
+            _ => Ok(Residues {
+                moduli: self.moduli.clone(),
+                residues: self
+                    .residues
+                    .iter()
+                    .zip(&other.residues)
+                    .zip(self.moduli.iter())
+                    .map(|((&a, &b), &m)| apply(a, b, m))
+                    .collect(),
+                constant: None,
+            }),
+        }
+    }
+
+    fn divisible(&self, monkey: usize, _divisor: u64) -> bool {
+        self.residues[monkey] == 0
+    }
+}
+
+/// Which number type to use for worry levels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// `u64`, with an error on overflow.
+    #[default]
+    U64,
+    /// `u128`, with an error on overflow.
+    U128,
+    /// Remainders modulo each monkey's divisor.
+    Residues,
+    /// Arbitrary-precision integers, which never overflow.
+    BigInt,
+}
+
+impl FromStr for Backend {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Backend, ParseError> {
+        match s {
+            "u64" => Ok(Backend::U64),
+            "u128" => Ok(Backend::U128),
+            "residues" => Ok(Backend::Residues),
+            "bigint" => Ok(Backend::BigInt),
+            _ => Err(ParseError::new(
+                "expected one of u64, u128, residues and bigint",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::expr::expression;
+
+    fn eval<W: Worry>(expr: &str, old: W) -> Result<W, WorryError> {
+        old.eval(&expression(expr).unwrap().1)
+    }
+
+    #[test]
+    fn checked_numbers() {
+        assert_eq!(eval("old * old + 3", 4u64), Ok(19));
+        assert_eq!(eval("old * old", u64::MAX), Err(WorryError::Overflow));
+        assert_eq!(
+            eval("old * old", u64::MAX as u128),
+            Ok(u64::MAX as u128 * u64::MAX as u128)
+        );
+        assert_eq!(eval("old - 5", 4u128), Err(WorryError::Negative));
+        assert_eq!(
+            eval("old / (old - 4)", 4u64),
+            Err(WorryError::DivisionByZero)
+        );
+        assert!(7u64.divisible(0, 7) && !8u128.divisible(0, 7));
+    }
+
+    #[test]
+    fn big_numbers() {
+        let big = BigUint::from(u64::MAX);
+        let square = eval("old * old", big.clone()).unwrap();
+        assert_eq!(square, &big * &big);
+        assert_eq!(eval("old / old", square.clone()), Ok(BigUint::from(1u32)));
+        assert_eq!(eval("old - (old + 1)", big), Err(WorryError::Negative));
+        assert!(square.divisible(0, 3) && !square.divisible(0, 2));
+    }
+
+    #[test]
+    fn residues() {
+        let divisors = [23, 19, 13];
+        let old = Residues::new(1234, &divisors);
+        let new = eval("(old + 7) * old - 3", old).unwrap();
+        let expected = (1234 + 7) * 1234 - 3;
+        assert_eq!(new, Residues::new(expected, &divisors));
+        assert_eq!(
+            new.to_string(),
+            format!("({} {} {})", expected % 23, expected % 19, expected % 13)
+        );
+        assert!(Residues::new(19 * 5, &divisors).divisible(1, 19));
+        assert!(!Residues::new(19 * 5, &divisors).divisible(0, 23));
+
+        let product = 23 * 19 * 13;
+        assert_eq!(new.modulo(product), Ok(new.clone()));
+        assert!(matches!(new.modulo(5), Err(WorryError::Unsupported(_))));
+        assert_eq!(
+            eval("old % 0", new.clone()),
+            Err(WorryError::DivisionByZero)
+        );
+        // Could be a remainder modulo zero, as far as the residues can tell
+        assert!(matches!(
+            eval(&format!("old % (old - old + {product})"), new.clone()),
+            Err(WorryError::Unsupported(_))
+        ));
+        assert!(matches!(
+            eval("old / 3", new),
+            Err(WorryError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+pub mod rules;
+pub mod visualize;
+
+use crate::grid::{Grid, Pos};
+use crate::search::{self, SearchResult};
+use crate::{Answer, Error, ParseError, Result, Solution};
+use rules::MoveRule;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use visualize::{Cell, Frame};
+
+#[derive(Debug)]
+struct NoPathError;
+
+impl From<NoPathError> for Error {
+    fn from(_: NoPathError) -> Error {
+        Error::no_solution("no path to the goal")
+    }
+}
+
+/// Called after each square the search expands, with the search so far and
+/// the squares waiting to be expanded
+type OnExpand<'a> = &'a mut dyn FnMut(&SearchResult<Pos>, &mut dyn Iterator<Item = &Pos>);
+
+/// Return cheapest path through elevation map under `rule`, including start and goal
+fn shortest_path(
+    start: Pos,
+    goal: Pos,
+    elevations: &Grid<usize>,
+    rule: &MoveRule,
+    on_expand: OnExpand,
+) -> Result<Vec<Pos>, NoPathError> {
+    search::astar_observed(
+        start,
+        |&pos| rule.moves_from(pos, elevations),
+        |&pos| rule.estimate(pos, goal, elevations),
+        |&pos| pos == goal,
+        on_expand,
+    )
+    .path()
+    .ok_or(NoPathError)
+}
+
+/// Return cheapest path under `rule` to `goal` from any square at the lowest elevation
+fn shortest_path_from_lowest(
+    goal: Pos,
+    elevations: &Grid<usize>,
+    rule: &MoveRule,
+    on_expand: OnExpand,
+) -> Result<Vec<Pos>, NoPathError> {
+    // Search backwards from the goal, so the first lowest square found is the
+    // closest one. Without a heuristic, A* is Dijkstra's algorithm
+    let mut path = search::astar_observed(
+        goal,
+        |&pos| rule.moves_to(pos, elevations),
+        |_| 0,
+        |&pos| elevations[pos] == 0,
+        on_expand,
+    )
+    .path()
+    .ok_or(NoPathError)?;
+    path.reverse();
+    Ok(path)
+}
+
+/// Draw `path` like the puzzle does: every square on it has an arrow pointing
+/// to the next one, the goal at its end is an E and other squares are dots.
+/// Diagonal steps are drawn with diagonal arrows.
+fn render_path(path: &[Pos], elevations: &Grid<usize>) -> String {
+    let mut squares: HashMap<Pos, char> = path
+        .windows(2)
+        .map(|step| {
+            let (Pos(x0, y0), Pos(x1, y1)) = (step[0], step[1]);
+            let arrow = match (x1.cmp(&x0), y1.cmp(&y0)) {
+                (Ordering::Equal, Ordering::Less) => '^',
+                (Ordering::Equal, _) => 'v',
+                (Ordering::Less, Ordering::Equal) => '<',
+                (Ordering::Greater, Ordering::Equal) => '>',
+                (Ordering::Less, Ordering::Less) => '↖',
+                (Ordering::Greater, Ordering::Less) => '↗',
+                (Ordering::Less, Ordering::Greater) => '↙',
+                (Ordering::Greater, Ordering::Greater) => '↘',
+            };
+            (step[0], arrow)
+        })
+        .collect();
+    if let Some(&goal) = path.last() {
+        squares.insert(goal, 'E');
+    }
+    elevations.render(|pos, _| squares.get(&pos).copied().unwrap_or('.'))
+}
+
+struct AllTheData {
+    elevations: Grid<usize>,
+    start: Pos,
+    goal: Pos,
+}
+
+fn parse_elevations(input: &str) -> Result<AllTheData, ParseError> {
+    let mut start = None;
+    let mut goal = None;
+    let elevations = Grid::parse(input, |pos, c| match c {
+        'S' => {
+            start = Some(pos);
+            Ok(0)
+        }
+        'E' => {
+            goal = Some(pos);
+            Ok((b'z' - b'a') as usize)
+        }
+        'a'..='z' => Ok(c as usize - b'a' as usize),
+        _ => Err("invalid elevation".to_string()),
+    })?;
+    Ok(AllTheData {
+        elevations,
+        start: start.ok_or_else(|| ParseError::new("no start position (S) found"))?,
+        goal: goal.ok_or_else(|| ParseError::new("no goal position (E) found"))?,
+    })
+}
+
+pub struct Day12 {
+    data: AllTheData,
+    rule: MoveRule,
+}
+
+impl Day12 {
+    /// Solve with other rules for moving than the puzzle's.
+    pub fn with_rule(self, rule: MoveRule) -> Day12 {
+        Day12 { rule, ..self }
+    }
+
+    /// The cheapest path from the start to the goal, including both.
+    pub fn path(&self) -> Result<Vec<Pos>> {
+        let AllTheData {
+            elevations,
+            start,
+            goal,
+        } = &self.data;
+        Ok(shortest_path(
+            *start,
+            *goal,
+            elevations,
+            &self.rule,
+            &mut |_, _| {},
+        )?)
+    }
+
+    /// The cheapest path to the goal from any square at the lowest elevation.
+    pub fn path_from_lowest(&self) -> Result<Vec<Pos>> {
+        let AllTheData {
+            elevations, goal, ..
+        } = &self.data;
+        Ok(shortest_path_from_lowest(
+            *goal,
+            elevations,
+            &self.rule,
+            &mut |_, _| {},
+        )?)
+    }
+
+    /// What taking the steps along `path` costs.
+    pub fn cost(&self, path: &[Pos]) -> usize {
+        let elevations = &self.data.elevations;
+        path.windows(2)
+            .map(|step| {
+                self.rule
+                    .step_cost(elevations[step[0]], elevations[step[1]])
+            })
+            .sum()
+    }
+
+    /// Draw `path` on the map with arrows, like the puzzle's illustrations.
+    pub fn render_path(&self, path: &[Pos]) -> String {
+        render_path(path, &self.data.elevations)
+    }
+
+    /// Find the same path as `path`, or `path_from_lowest` if `from_lowest`,
+    /// calling `on_frame` every `every` expanded squares with the state of the
+    /// search, and once more at the end with the path.
+    pub fn visualize(
+        &self,
+        from_lowest: bool,
+        every: usize,
+        mut on_frame: impl FnMut(&Frame),
+    ) -> Result<Vec<Pos>> {
+        let AllTheData {
+            elevations,
+            start,
+            goal,
+        } = &self.data;
+        let mut frame = Frame {
+            cells: elevations.map(|_| Cell::Unseen),
+            expanded: 0,
+        };
+        let mut on_expand = |result: &SearchResult<Pos>, fringe: &mut dyn Iterator<Item = &Pos>| {
+            let last = *result.explored.last().expect("called after expanding");
+            frame.cells[last] = Cell::Explored;
+            frame.expanded = result.explored.len();
+            if frame.expanded.is_multiple_of(every.max(1)) {
+                for &pos in fringe {
+                    frame.cells[pos] = Cell::Fringe;
+                }
+                on_frame(&frame);
+            }
+        };
+        let path = if from_lowest {
+            shortest_path_from_lowest(*goal, elevations, &self.rule, &mut on_expand)?
+        } else {
+            shortest_path(*start, *goal, elevations, &self.rule, &mut on_expand)?
+        };
+        // The goal is expanded too, without a call to `on_expand`
+        frame.expanded += 1;
+        for &pos in &path {
+            frame.cells[pos] = Cell::Path;
+        }
+        on_frame(&frame);
+        Ok(path)
+    }
+
+    /// Draw a frame of `visualize` in the terminal, with the squares coloured
+    /// by how far the search got.
+    pub fn render_frame(&self, frame: &Frame) -> String {
+        let AllTheData {
+            elevations,
+            start,
+            goal,
+        } = &self.data;
+        visualize::to_ansi(&frame.cells, |pos| match pos {
+            pos if pos == *start => 'S',
+            pos if pos == *goal => 'E',
+            pos => (b'a' + elevations[pos] as u8) as char,
+        })
+    }
+}
+
+impl Solution for Day12 {
+    fn parse(input: &str) -> Result<Day12, ParseError> {
+        Ok(Day12 {
+            data: parse_elevations(input)?,
+            rule: MoveRule::default(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(self.cost(&self.path()?).into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(self.cost(&self.path_from_lowest()?).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rules::Cost;
+
+    pub(super) const SAMPLE_INPUT: &str = "
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day12::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "31");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day12::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "29");
+    }
+
+    #[test]
+    fn invalid_elevation() {
+        let e = Day12::parse(&SAMPLE_INPUT.replace("accszExk", "accs!Exk"))
+            .err()
+            .unwrap();
+        assert_eq!(e.message, "invalid elevation");
+        let location = e.location.unwrap();
+        assert_eq!((location.line, location.column), (4, 5));
+    }
+
+    #[test]
+    fn render_sample_paths() {
+        let day = Day12::parse(SAMPLE_INPUT).unwrap();
+        let path = day.path().unwrap();
+        assert_eq!(path.first(), Some(&Pos(0, 0)));
+        assert_eq!(path.last(), Some(&Pos(5, 2)));
+        // Another of the shortest paths than the one in the puzzle
+        assert_eq!(
+            day.render_path(&path),
+            "\
+>>vv<<<<
+..vvv<<^
+..vv>E^^
+..v>>>^^
+..>>>>>^
+"
+        );
+        let path = day.path_from_lowest().unwrap();
+        assert_eq!(path.len(), 30);
+        assert_eq!(day.data.elevations[path[0]], 0);
+        assert_eq!(
+            day.render_path(&path).matches(['<', '>', '^', 'v']).count(),
+            29
+        );
+    }
+
+    #[test]
+    fn other_rules() {
+        let day = Day12::parse(SAMPLE_INPUT).unwrap();
+        // With any climb allowed, the path goes straight to the goal
+        let day = day.with_rule(MoveRule {
+            max_climb: 25,
+            ..MoveRule::default()
+        });
+        assert_eq!(day.part1().unwrap().to_string(), "7");
+
+        let day = day.with_rule(MoveRule {
+            diagonal: true,
+            ..MoveRule::default()
+        });
+        let path = day.path().unwrap();
+        assert_eq!(day.cost(&path), 27);
+        assert_eq!(day.part2().unwrap().to_string(), "26");
+        assert!(day.render_path(&path).contains(['↖', '↘']));
+
+        let day = day.with_rule(MoveRule {
+            cost: Cost::CLIMBING,
+            ..MoveRule::default()
+        });
+        // Everything between a and z has to be climbed, but no more
+        assert_eq!(day.part1().unwrap().to_string(), "25");
+        assert_eq!(day.part2().unwrap().to_string(), "25");
+
+        let day = day.with_rule(MoveRule {
+            max_descent: Some(0),
+            ..MoveRule::default()
+        });
+        // The spiral up to the goal never goes down
+        assert_eq!(day.part1().unwrap().to_string(), "31");
+        let day = day.with_rule(MoveRule {
+            max_climb: 0,
+            ..MoveRule::default()
+        });
+        assert!(day.part1().is_err());
+    }
+}
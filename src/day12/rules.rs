@@ -0,0 +1,222 @@
+//! Rules for which steps can be taken on the map, and what they cost.
+
+use crate::grid::{Grid, Pos};
+use crate::ParseError;
+use std::str::FromStr;
+
+/// What a step costs: `step` for taking it, plus `climb` for every unit of
+/// elevation it goes up and `descent` for every unit it goes down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cost {
+    pub step: usize,
+    pub climb: usize,
+    pub descent: usize,
+}
+
+impl Cost {
+    /// Every step costs 1, like in the puzzle.
+    pub const STEPS: Cost = Cost {
+        step: 1,
+        climb: 0,
+        descent: 0,
+    };
+
+    /// Only climbing costs, one per unit of elevation.
+    pub const CLIMBING: Cost = Cost {
+        step: 0,
+        climb: 1,
+        descent: 0,
+    };
+}
+
+impl Default for Cost {
+    fn default() -> Self {
+        Cost::STEPS
+    }
+}
+
+impl FromStr for Cost {
+    type Err = ParseError;
+
+    /// Parse `steps`, `climbing` or the three weights, like `1,2,0`.
+    fn from_str(s: &str) -> Result<Cost, ParseError> {
+        match s {
+            "steps" => return Ok(Cost::STEPS),
+            "climbing" => return Ok(Cost::CLIMBING),
+            _ => {}
+        }
+        let weights = s
+            .split(',')
+            .map(|n| {
+                n.trim()
+                    .parse()
+                    .map_err(|_| ParseError::at(s, n, "expected a number"))
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+        match weights[..] {
+            [step, climb, descent] => Ok(Cost {
+                step,
+                climb,
+                descent,
+            }),
+            _ => Err(ParseError::new(
+                "expected steps, climbing or <step>,<climb>,<descent>",
+            )),
+        }
+    }
+}
+
+/// Which steps can be taken and what they cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveRule {
+    /// How far up a single step can go.
+    pub max_climb: usize,
+    /// How far down a single step can go, without limit if None.
+    pub max_descent: Option<usize>,
+    /// Whether steps can also go to the four diagonal neighbours.
+    pub diagonal: bool,
+    pub cost: Cost,
+}
+
+impl Default for MoveRule {
+    /// The puzzle's rule: climb at most one, descend any distance, only move
+    /// up, down, left or right, and count the steps.
+    fn default() -> Self {
+        MoveRule {
+            max_climb: 1,
+            max_descent: None,
+            diagonal: false,
+            cost: Cost::STEPS,
+        }
+    }
+}
+
+impl MoveRule {
+    /// Whether a step from elevation `from` to elevation `to` is allowed.
+    pub fn allows(&self, from: usize, to: usize) -> bool {
+        if to >= from {
+            to - from <= self.max_climb
+        } else {
+            self.max_descent.is_none_or(|max| from - to <= max)
+        }
+    }
+
+    /// The cost of a step from elevation `from` to elevation `to`.
+    pub fn step_cost(&self, from: usize, to: usize) -> usize {
+        let Cost {
+            step,
+            climb,
+            descent,
+        } = self.cost;
+        step + climb * to.saturating_sub(from) + descent * from.saturating_sub(to)
+    }
+
+    fn neighbours<'a>(
+        &self,
+        pos: Pos,
+        elevations: &'a Grid<usize>,
+    ) -> Box<dyn Iterator<Item = Pos> + 'a> {
+        if self.diagonal {
+            Box::new(elevations.neighbours8(pos))
+        } else {
+            Box::new(elevations.neighbours4(pos))
+        }
+    }
+
+    /// Squares that can be reached in one step from `pos`, with the cost of
+    /// getting there.
+    pub fn moves_from<'a>(
+        &'a self,
+        pos: Pos,
+        elevations: &'a Grid<usize>,
+    ) -> impl Iterator<Item = (Pos, usize)> + 'a {
+        let from = elevations[pos];
+        self.neighbours(pos, elevations)
+            .filter_map(move |neighbour| {
+                let to = elevations[neighbour];
+                self.allows(from, to)
+                    .then(|| (neighbour, self.step_cost(from, to)))
+            })
+    }
+
+    /// Squares from which `pos` can be reached in one step, with the cost of
+    /// that step.
+    pub fn moves_to<'a>(
+        &'a self,
+        pos: Pos,
+        elevations: &'a Grid<usize>,
+    ) -> impl Iterator<Item = (Pos, usize)> + 'a {
+        let to = elevations[pos];
+        self.neighbours(pos, elevations)
+            .filter_map(move |neighbour| {
+                let from = elevations[neighbour];
+                self.allows(from, to)
+                    .then(|| (neighbour, self.step_cost(from, to)))
+            })
+    }
+
+    /// A lower bound for the cost of getting from `start` to `goal`.
+    pub fn estimate(&self, start: Pos, goal: Pos, elevations: &Grid<usize>) -> usize {
+        let (dx, dy) = (start.0.abs_diff(goal.0), start.1.abs_diff(goal.1));
+        let distance = if self.diagonal { dx.max(dy) } else { dx + dy };
+        let (from, to) = (elevations[start], elevations[goal]);
+        let (climb, descent) = (to.saturating_sub(from), from.saturating_sub(to));
+        let steps = match (self.max_climb, self.max_descent) {
+            (0, _) if climb > 0 => distance,
+            (_, Some(0)) if descent > 0 => distance,
+            (max_climb, max_descent) => distance
+                .max(climb.div_ceil(max_climb.max(1)))
+                .max(max_descent.map_or(0, |max| descent.div_ceil(max.max(1)))),
+        };
+        self.cost.step * steps + self.cost.climb * climb + self.cost.descent * descent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps() {
+        let rule = MoveRule::default();
+        assert!(rule.allows(3, 4) && !rule.allows(3, 5) && rule.allows(25, 0));
+        let rule = MoveRule {
+            max_climb: 2,
+            max_descent: Some(1),
+            ..rule
+        };
+        assert!(rule.allows(3, 5) && rule.allows(3, 2) && !rule.allows(3, 1));
+    }
+
+    #[test]
+    fn costs() {
+        let rule = MoveRule {
+            cost: "1,10,2".parse().unwrap(),
+            ..MoveRule::default()
+        };
+        assert_eq!(rule.step_cost(3, 4), 11);
+        assert_eq!(rule.step_cost(3, 0), 7);
+        assert_eq!(rule.step_cost(3, 3), 1);
+        assert_eq!("climbing".parse(), Ok(Cost::CLIMBING));
+        assert!("1,2".parse::<Cost>().is_err());
+        assert!("1,x,2".parse::<Cost>().is_err());
+    }
+
+    #[test]
+    fn moves() {
+        let elevations = Grid::new(vec![0, 1, 2, 5, 1, 0, 0, 0, 0], 3, 3);
+        let rule = MoveRule::default();
+        let mut moves: Vec<_> = rule.moves_from(Pos(1, 1), &elevations).collect();
+        moves.sort();
+        assert_eq!(moves, [(Pos(1, 0), 1), (Pos(1, 2), 1), (Pos(2, 1), 1)]);
+        let to: Vec<_> = rule.moves_to(Pos(2, 0), &elevations).collect();
+        assert_eq!(to, [(Pos(1, 0), 1)]);
+
+        let rule = MoveRule {
+            diagonal: true,
+            ..rule
+        };
+        assert_eq!(rule.moves_from(Pos(0, 0), &elevations).count(), 2);
+        assert_eq!(rule.estimate(Pos(0, 0), Pos(2, 2), &elevations), 2);
+    }
+}
@@ -0,0 +1,92 @@
+//! Drawing the hill-climbing search as it spreads over the map.
+
+use crate::grid::{Grid, Pos};
+
+/// How far the search got at a square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Unseen,
+    /// Reached, and waiting to be expanded.
+    Fringe,
+    Explored,
+    /// On the path that was found.
+    Path,
+}
+
+/// The state of the search after some squares were expanded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub cells: Grid<Cell>,
+    /// How many squares were expanded.
+    pub expanded: usize,
+}
+
+/// Background colours for a terminal supporting 24-bit colour.
+fn background(cell: Cell) -> Option<[u8; 3]> {
+    match cell {
+        Cell::Unseen => None,
+        Cell::Fringe => Some([230, 170, 20]),
+        Cell::Explored => Some([40, 70, 130]),
+        Cell::Path => Some([30, 150, 60]),
+    }
+}
+
+/// `cells` with `label` for each square, coloured by how far the search got.
+pub fn to_ansi(cells: &Grid<Cell>, label: impl Fn(Pos) -> char) -> String {
+    let mut res = String::new();
+    for y in 0..cells.height() {
+        for pos in cells.row(y) {
+            match background(cells[pos]) {
+                Some([r, g, b]) => res.push_str(&format!("\x1b[48;2;{r};{g};{b}m")),
+                None => res.push_str("\x1b[49m"),
+            }
+            res.push(label(pos));
+        }
+        res.push_str("\x1b[0m\n");
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day12::{tests::SAMPLE_INPUT, Day12};
+    use crate::Solution;
+
+    fn count(frame: &Frame, cell: Cell) -> usize {
+        frame.cells.values().filter(|&&c| c == cell).count()
+    }
+
+    #[test]
+    fn frames() {
+        let day = Day12::parse(SAMPLE_INPUT).unwrap();
+        let mut frames = Vec::new();
+        let path = day
+            .visualize(false, 5, |frame| frames.push(frame.clone()))
+            .unwrap();
+        assert_eq!(path, day.path().unwrap());
+
+        let (last, frames) = frames.split_last().unwrap();
+        assert!(!frames.is_empty());
+        for (i, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.expanded, 5 * (i + 1));
+            assert_eq!(count(frame, Cell::Explored), frame.expanded);
+            assert!(count(frame, Cell::Fringe) > 0);
+            assert_eq!(count(frame, Cell::Path), 0);
+        }
+        assert_eq!(count(last, Cell::Path), path.len());
+
+        let drawn = day.render_frame(last);
+        assert!(drawn.starts_with("\x1b[48;2;30;150;60mS"));
+        assert_eq!(drawn.lines().count(), 5);
+    }
+
+    #[test]
+    fn ansi() {
+        let cells = Grid::new(vec![Cell::Unseen, Cell::Fringe], 2, 1);
+        assert_eq!(
+            to_ansi(&cells, |pos| if pos.0 == 0 { 'a' } else { 'b' }),
+            "\x1b[49ma\x1b[48;2;230;170;20mb\x1b[0m\n"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// Scores are spelled out as "shape score + outcome score"
+#![allow(clippy::identity_op)]
+
+use crate::{Answer, ParseError, Result, Solution};
+use std::collections::HashMap;
+
+fn part1_score(rounds: &[String]) -> u64 {
+    let scores = HashMap::from([
+        ("A X", 1 + 3),
+        ("A Y", 2 + 6),
+        ("A Z", 3 + 0),
+        ("B X", 1 + 0),
+        ("B Y", 2 + 3),
+        ("B Z", 3 + 6),
+        ("C X", 1 + 6),
+        ("C Y", 2 + 0),
+        ("C Z", 3 + 3),
+    ]);
+    rounds.iter().map(|l| scores[l.as_str()]).sum::<u64>()
+}
+
+fn part2_score(rounds: &[String]) -> u64 {
+    let scores = HashMap::from([
+        ("A X", 3 + 0),
+        ("A Y", 1 + 3),
+        ("A Z", 2 + 6),
+        ("B X", 1 + 0),
+        ("B Y", 2 + 3),
+        ("B Z", 3 + 6),
+        ("C X", 2 + 0),
+        ("C Y", 3 + 3),
+        ("C Z", 1 + 6),
+    ]);
+    rounds.iter().map(|l| scores[l.as_str()]).sum::<u64>()
+}
+
+pub struct Day2 {
+    rounds: Vec<String>,
+}
+
+impl Solution for Day2 {
+    fn parse(input: &str) -> Result<Day2, ParseError> {
+        let rounds = input
+            .trim()
+            .lines()
+            .map(|line| match line.as_bytes() {
+                [b'A'..=b'C', b' ', b'X'..=b'Z'] => Ok(line.to_string()),
+                _ => Err(ParseError::at(input, line, "expected a round like \"A Y\"")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Day2 { rounds })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(part1_score(&self.rounds).into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(part2_score(&self.rounds).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+A Y
+B X
+C Z
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day2::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "15");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day2::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "12");
+    }
+}
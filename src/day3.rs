@@ -0,0 +1,113 @@
+use crate::{Answer, Error, ParseError, Result, Solution};
+use std::collections::HashSet;
+
+static PRIORITIES: [char; 52] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L',
+    'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+fn get_doublet(contents: &str) -> Result<char> {
+    let contents: Vec<_> = contents.chars().collect();
+    let len = contents.len();
+    let compartment1: HashSet<_> = contents[..len / 2].iter().cloned().collect();
+    let compartment2: HashSet<_> = contents[len / 2..].iter().cloned().collect();
+    let intersection = compartment1.intersection(&compartment2);
+    intersection.last().copied().ok_or_else(|| {
+        let contents: String = contents.iter().collect();
+        Error::no_solution(format!("no item in both compartments of {contents}"))
+    })
+}
+
+fn priority(c: char) -> u64 {
+    1 + PRIORITIES
+        .iter()
+        .position(|o| &c == o)
+        .expect("items are validated when parsing") as u64
+}
+
+pub struct Day3 {
+    rucksacks: Vec<String>,
+}
+
+impl Solution for Day3 {
+    fn parse(input: &str) -> Result<Day3, ParseError> {
+        let rucksacks = input
+            .trim()
+            .lines()
+            .map(|line| {
+                if let Some(i) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+                    Err(ParseError::at(input, &line[i..], "items must be letters"))
+                } else if !line.len().is_multiple_of(2) {
+                    Err(ParseError::at(input, line, "odd number of items"))
+                } else {
+                    Ok(line.to_string())
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Day3 { rucksacks })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        let mut total_priority = 0;
+        for rucksack in &self.rucksacks {
+            total_priority += priority(get_doublet(rucksack)?);
+        }
+        Ok(total_priority.into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(badge_priorities(&self.rucksacks)?.into())
+    }
+}
+
+fn badge_priorities(rucksacks: &[String]) -> Result<u64> {
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(Error::no_solution(
+            "rucksacks can not be split into groups of three",
+        ));
+    }
+    let mut total_priority = 0;
+    let mut lines = rucksacks.iter();
+    while let Some(rucksack1) = lines.next() {
+        let rucksack1: HashSet<_> = rucksack1.chars().collect();
+        let rucksack2: HashSet<_> = lines.next().unwrap().chars().collect();
+        let rucksack3: HashSet<_> = lines.next().unwrap().chars().collect();
+        let badge_item: char = rucksack1
+            .intersection(&rucksack2)
+            .cloned()
+            .collect::<HashSet<_>>()
+            .intersection(&rucksack3)
+            .next()
+            .copied()
+            .ok_or_else(|| Error::no_solution("no badge item shared by a group"))?;
+        total_priority += priority(badge_item);
+    }
+    Ok(total_priority)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day3::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "157");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day3::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "70");
+    }
+}
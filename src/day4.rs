@@ -0,0 +1,86 @@
+use crate::error::parse_all;
+use crate::{Answer, ParseError, Result, Solution};
+use nom::{
+    bytes::complete::tag, character::complete, character::complete::newline,
+    multi::separated_list1, sequence::separated_pair, IResult,
+};
+
+struct Range {
+    start: u32,
+    end: u32,
+}
+
+fn full_overlap(a: &Range, b: &Range) -> bool {
+    a.start <= b.start && a.end >= b.end || a.start >= b.start && a.end <= b.end
+}
+fn overlap(a: &Range, b: &Range) -> bool {
+    a.start <= b.start && a.end >= b.start || a.start >= b.start && a.start <= b.end
+}
+
+fn range(input: &str) -> IResult<&str, Range> {
+    let (input, (start, end)) = separated_pair(complete::u32, tag("-"), complete::u32)(input)?;
+    Ok((input, Range { start, end }))
+}
+fn range_pair(input: &str) -> IResult<&str, (Range, Range)> {
+    let (input, (range_a, range_b)) = separated_pair(range, tag(","), range)(input)?;
+    Ok((input, (range_a, range_b)))
+}
+fn range_pairs(input: &str) -> IResult<&str, Vec<(Range, Range)>> {
+    let (input, res) = separated_list1(newline, range_pair)(input)?;
+    Ok((input, res))
+}
+
+pub struct Day4 {
+    assignments: Vec<(Range, Range)>,
+}
+
+impl Solution for Day4 {
+    fn parse(input: &str) -> Result<Day4, ParseError> {
+        let assignments = parse_all(input, input.trim(), range_pairs)?;
+        Ok(Day4 { assignments })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(self
+            .assignments
+            .iter()
+            .filter(|(a, b)| full_overlap(a, b))
+            .count()
+            .into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(self
+            .assignments
+            .iter()
+            .filter(|(a, b)| overlap(a, b))
+            .count()
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day4::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "2");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day4::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "4");
+    }
+}
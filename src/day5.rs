@@ -1,25 +1,15 @@
+use crate::error::parse_all;
+use crate::{Answer, Error, ParseError, Result, Solution};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete,
     character::complete::{alpha1, newline, space1},
+    combinator::verify,
     multi::separated_list1,
     sequence::delimited,
     IResult,
 };
-use std::fs;
-
-const SAMPLE_INPUT: &str = "
-    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2
-";
 
 fn krate(input: &str) -> IResult<&str, Option<&str>> {
     let (input, c) = alt((tag("   "), delimited(tag("["), alpha1, tag("]"))))(input)?;
@@ -35,10 +25,10 @@ fn crates_line(input: &str) -> IResult<&str, Vec<Option<&str>>> {
 fn crates_lines(input: &str) -> IResult<&str, Vec<Vec<Option<&str>>>> {
     separated_list1(newline, crates_line)(input)
 }
-fn stacks(input: &str) -> IResult<&str, Vec<Vec<&str>>> {
+fn stacks(input: &str) -> IResult<&str, Vec<Vec<String>>> {
     let (input, stacks_lines) = crates_lines(input)?;
 
-    let mut stacks: Vec<Vec<&str>> = Vec::new();
+    let mut stacks: Vec<Vec<String>> = Vec::new();
     for stacks_line in stacks_lines {
         if stacks.is_empty() {
             for _ in 0..stacks_line.len() {
@@ -50,7 +40,7 @@ fn stacks(input: &str) -> IResult<&str, Vec<Vec<&str>>> {
             .enumerate()
             .filter_map(|(i, c)| c.map(|c| (i, c)))
         {
-            stacks[i].push(krate)
+            stacks[i].push(krate.to_string())
         }
     }
 
@@ -73,13 +63,17 @@ struct Instruction {
     to: usize,
 }
 
+fn stack_number(input: &str) -> IResult<&str, u32> {
+    verify(complete::u32, |&n| n > 0)(input)
+}
+
 fn instruction(input: &str) -> IResult<&str, Instruction> {
     let (input, _) = tag("move ")(input)?;
     let (input, count) = complete::u32(input)?;
     let (input, _) = tag(" from ")(input)?;
-    let (input, from) = complete::u32(input)?;
+    let (input, from) = stack_number(input)?;
     let (input, _) = tag(" to ")(input)?;
-    let (input, to) = complete::u32(input)?;
+    let (input, to) = stack_number(input)?;
     Ok((
         input,
         Instruction {
@@ -93,7 +87,7 @@ fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
     separated_list1(newline, instruction)(input)
 }
 
-fn parse(input: &str) -> IResult<&str, (Vec<Vec<&str>>, Vec<Instruction>)> {
+fn parse(input: &str) -> IResult<&str, (Vec<Vec<String>>, Vec<Instruction>)> {
     let (input, stacks) = stacks(input)?;
     let (input, _) = newline(input)?;
     let (input, _numbers) = numbers(input)?;
@@ -117,10 +111,63 @@ fn trim(input: &str) -> &str {
 //     }
 // }
 
-fn part1(input: &str) -> String {
-    let (_input, (mut stacks, instructions)) = parse(input).unwrap();
+fn top_crates(stacks: Vec<Vec<String>>) -> Result<String> {
+    let mut res = Vec::new();
+    for (i, mut stack) in stacks.into_iter().enumerate() {
+        let krate = stack
+            .pop()
+            .ok_or_else(|| Error::no_solution(format!("stack {} ends up empty", i + 1)))?;
+        res.push(krate)
+    }
+    Ok(res.join(""))
+}
+
+fn check_stacks(stacks: &[Vec<String>], instruction: &Instruction) -> Result<()> {
+    for stack in [instruction.from, instruction.to] {
+        if stack >= stacks.len() {
+            return Err(Error::no_solution(format!(
+                "there is no stack {}",
+                stack + 1
+            )));
+        }
+    }
+    if stacks[instruction.from].len() < instruction.count {
+        return Err(Error::no_solution(format!(
+            "can not move {} crates from stack {}",
+            instruction.count,
+            instruction.from + 1
+        )));
+    }
+    Ok(())
+}
+
+pub struct Day5 {
+    stacks: Vec<Vec<String>>,
+    instructions: Vec<Instruction>,
+}
+
+impl Solution for Day5 {
+    fn parse(input: &str) -> Result<Day5, ParseError> {
+        let (stacks, instructions) = parse_all(input, trim(input), parse)?;
+        Ok(Day5 {
+            stacks,
+            instructions,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(crate_mover_9000(self.stacks.clone(), &self.instructions)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(crate_mover_9001(self.stacks.clone(), &self.instructions)?.into())
+    }
+}
 
-    for Instruction { count, from, to } in instructions {
+fn crate_mover_9000(mut stacks: Vec<Vec<String>>, instructions: &[Instruction]) -> Result<String> {
+    for instruction in instructions {
+        check_stacks(&stacks, instruction)?;
+        let &Instruction { count, from, to } = instruction;
         // eprint_stacks(&stacks);
         // eprintln!("Move {count} from {from} to {to}");
         for _ in 0..count {
@@ -129,17 +176,13 @@ fn part1(input: &str) -> String {
         }
     }
 
-    let mut res = Vec::new();
-    for mut stack in stacks {
-        res.push(stack.pop().unwrap().to_string())
-    }
-    res.join("")
+    top_crates(stacks)
 }
 
-fn part2(input: &str) -> String {
-    let (_input, (mut stacks, instructions)) = parse(input).unwrap();
-
-    for Instruction { count, from, to } in instructions {
+fn crate_mover_9001(mut stacks: Vec<Vec<String>>, instructions: &[Instruction]) -> Result<String> {
+    for instruction in instructions {
+        check_stacks(&stacks, instruction)?;
+        let &Instruction { count, from, to } = instruction;
         // eprint_stacks(&stacks);
         // eprintln!("Move {count} from {from} to {to}");
         let len = stacks[from].len();
@@ -147,19 +190,34 @@ fn part2(input: &str) -> String {
         stacks[to].extend(cache);
     }
 
-    let mut res = Vec::new();
-    for mut stack in stacks {
-        res.push(stack.pop().unwrap().to_string())
-    }
-    res.join("")
+    top_crates(stacks)
 }
 
-fn main() {
-    let full_input = fs::read_to_string("input/day5").unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
 
-    println!("Part 1 (sample input): {}", part1(trim(SAMPLE_INPUT)));
-    println!("Part 1 (full input): {}", part1(trim(&full_input)));
+    #[test]
+    fn part1_sample() {
+        let day = Day5::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "CMZ");
+    }
 
-    println!("Part 2 (sample input): {}", part2(trim(SAMPLE_INPUT)));
-    println!("Part 2 (full input): {}", part2(trim(&full_input)));
+    #[test]
+    fn part2_sample() {
+        let day = Day5::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "MCD");
+    }
 }
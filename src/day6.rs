@@ -0,0 +1,83 @@
+use crate::{Answer, Error, ParseError, Result, Solution};
+use std::collections::{HashSet, VecDeque};
+
+fn find_marker(input: &str, marker_size: usize) -> Option<usize> {
+    let mut buffer = VecDeque::with_capacity(marker_size);
+    let mut chars = input.chars();
+    for _ in 0..marker_size {
+        buffer.push_back(chars.next()?);
+    }
+    for (i, c) in chars.enumerate() {
+        let unique_chars: HashSet<_> = buffer.iter().cloned().collect();
+        if unique_chars.len() == marker_size {
+            return Some(i + marker_size);
+        }
+        buffer.pop_front();
+        buffer.push_back(c);
+    }
+    None
+}
+
+pub struct Day6 {
+    datastream: String,
+}
+
+impl Solution for Day6 {
+    fn parse(input: &str) -> Result<Day6, ParseError> {
+        Ok(Day6 {
+            datastream: input.trim().to_string(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        find_marker(&self.datastream, 4)
+            .map(Answer::from)
+            .ok_or_else(|| Error::no_solution("no start-of-packet marker found"))
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        find_marker(&self.datastream, 14)
+            .map(Answer::from)
+            .ok_or_else(|| Error::no_solution("no start-of-message marker found"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day6::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "7");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day6::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "19");
+    }
+
+    #[test]
+    fn find_marker_examples() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (datastream, packet, message) in examples {
+            assert_eq!(find_marker(datastream, 4), Some(packet));
+            assert_eq!(find_marker(datastream, 14), Some(message));
+        }
+    }
+
+    #[test]
+    fn find_marker_too_short() {
+        assert_eq!(find_marker("abc", 4), None);
+        assert_eq!(find_marker("aaaaaaaa", 4), None);
+    }
+}
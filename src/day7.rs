@@ -0,0 +1,137 @@
+use crate::{Answer, Error, ParseError, Result, Solution};
+use std::collections::HashMap;
+
+fn get_dir_sizes(input: &str) -> Result<HashMap<String, u64>, ParseError> {
+    let mut cd = vec![""];
+    let mut dir_sizes: HashMap<String, u64> = HashMap::new();
+    for line in input.trim().lines() {
+        // eprintln!("{}", line);
+        if line.starts_with("$ cd") {
+            // change current directory
+            if let Some((_cmd, dir_name)) = line.rsplit_once(" ") {
+                match dir_name {
+                    "/" => {
+                        cd = vec![""];
+                    }
+                    ".." => {
+                        if cd.len() == 1 {
+                            return Err(ParseError::at(input, line, "can not leave /"));
+                        }
+                        cd.pop();
+                    }
+                    _ => {
+                        cd.push(dir_name);
+                    }
+                };
+            }
+        } else if line.starts_with("$ ls") {
+            // TODO: sanity check?
+        } else if let Some((size, _filename)) = line.split_once(" ") {
+            if size == "dir" {
+                continue;
+            }
+            let size: u64 = size
+                .parse()
+                .map_err(|e| ParseError::at(input, line, format!("invalid file size: {e}")))?;
+            // Add entries for this file in current dir and all parent dirs
+            for end in 1..=cd.len() {
+                *dir_sizes.entry(cd[0..end].join("/")).or_insert(0) += size;
+            }
+        } else {
+            return Err(ParseError::at(input, line, "unrecognized line"));
+        }
+    }
+    Ok(dir_sizes)
+}
+
+pub struct Day7 {
+    dir_sizes: HashMap<String, u64>,
+}
+
+impl Solution for Day7 {
+    fn parse(input: &str) -> Result<Day7, ParseError> {
+        Ok(Day7 {
+            dir_sizes: get_dir_sizes(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        let mut res = 0;
+        for &size in self.dir_sizes.values() {
+            if size <= 100000 {
+                res += size;
+            }
+        }
+        Ok(res.into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        const ALLOWED_USED_SPACE: u64 = 40_000_000;
+        let used_space = self.dir_sizes.get("").copied().unwrap_or(0);
+        let min_removed_dirsize = used_space.saturating_sub(ALLOWED_USED_SPACE);
+
+        let mut dir_sizes: Vec<_> = self.dir_sizes.values().copied().collect();
+        dir_sizes.sort_unstable();
+        for size in dir_sizes {
+            if size >= min_removed_dirsize {
+                return Ok(size.into());
+            }
+        }
+        Err(Error::no_solution(
+            "no directory suitable for deletion found",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day7::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "95437");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day7::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "24933642");
+    }
+
+    #[test]
+    fn dir_sizes_sample() {
+        let dir_sizes = get_dir_sizes(SAMPLE_INPUT).unwrap();
+        assert_eq!(dir_sizes.len(), 4);
+        assert_eq!(dir_sizes["/a/e"], 584);
+        assert_eq!(dir_sizes["/a"], 94853);
+        assert_eq!(dir_sizes["/d"], 24933642);
+        assert_eq!(dir_sizes[""], 48381165);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::grid::{Grid, Pos};
+use crate::{Answer, ParseError, Result, Solution};
+
+type Forest = Grid<u32>;
+
+fn parse_input(input: &str) -> Result<Forest, ParseError> {
+    Grid::parse(input, |_, c| {
+        c.to_digit(10)
+            .ok_or_else(|| "tree heights must be digits".to_string())
+    })
+}
+
+fn visible_trees(forest: &Forest) -> usize {
+    let mut seen = Grid::filled(false, forest.width(), forest.height());
+
+    // left to right
+    for y in 0..forest.height() {
+        let mut highest = -1;
+        for pos in forest.row(y) {
+            let tree = forest[pos];
+            if tree as i32 > highest {
+                highest = tree as i32;
+                seen[pos] = true;
+            }
+        }
+    }
+    // right to left
+    for y in 0..forest.height() {
+        let mut highest = -1;
+        for pos in forest.row(y).rev() {
+            let tree = forest[pos];
+            if tree as i32 > highest {
+                highest = tree as i32;
+                seen[pos] = true;
+            }
+        }
+    }
+    // top to bottom
+    for x in 0..forest.width() {
+        let mut highest = -1;
+        for pos in forest.column(x) {
+            let tree = forest[pos];
+            if tree as i32 > highest {
+                highest = tree as i32;
+                seen[pos] = true;
+            }
+        }
+    }
+    // bottom to top
+    for x in 0..forest.width() {
+        let mut highest = -1;
+        for pos in forest.column(x).rev() {
+            let tree = forest[pos];
+            if tree as i32 > highest {
+                highest = tree as i32;
+                seen[pos] = true;
+            }
+        }
+    }
+
+    seen.values().filter(|&&v| v).count()
+}
+
+fn add_scenic_factor(
+    scenic_scores: &mut Grid<u32>,
+    positions: impl Iterator<Item = Pos>,
+    forest: &Forest,
+) {
+    let mut viewing_distances = [0; 10];
+    for pos in positions {
+        let tree = forest[pos];
+        scenic_scores[pos] *= viewing_distances[tree as usize];
+        for (height, distance) in viewing_distances.iter_mut().enumerate() {
+            if height <= tree as usize {
+                *distance = 1;
+            } else {
+                *distance += 1;
+            }
+        }
+    }
+}
+
+fn max_scenic_score(forest: &Forest) -> u32 {
+    let mut scenic_scores = Grid::filled(1, forest.width(), forest.height());
+
+    for y in 0..forest.height() {
+        // left to right
+        let positions = forest.row(y);
+        add_scenic_factor(&mut scenic_scores, positions, forest);
+    }
+    for y in 0..forest.height() {
+        // right to left
+        let positions = forest.row(y).rev();
+        add_scenic_factor(&mut scenic_scores, positions, forest);
+    }
+    for x in 0..forest.width() {
+        // top to bottom
+        let positions = forest.column(x);
+        add_scenic_factor(&mut scenic_scores, positions, forest);
+    }
+    for x in 0..forest.width() {
+        // bottom to top
+        let positions = forest.column(x).rev();
+        add_scenic_factor(&mut scenic_scores, positions, forest);
+    }
+
+    scenic_scores.values().copied().max().unwrap_or(0)
+}
+
+pub struct Day8 {
+    forest: Forest,
+}
+
+impl Solution for Day8 {
+    fn parse(input: &str) -> Result<Day8, ParseError> {
+        Ok(Day8 {
+            forest: parse_input(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(visible_trees(&self.forest).into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(max_scenic_score(&self.forest).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day8::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "21");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day8::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "8");
+    }
+
+    #[test]
+    fn invalid_height() {
+        let e = Day8::parse(&SAMPLE_INPUT.replace("65332", "65x32"))
+            .err()
+            .unwrap();
+        let location = e.location.unwrap();
+        assert_eq!((location.line, location.column), (4, 3));
+    }
+}
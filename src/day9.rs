@@ -1,21 +1,17 @@
+use crate::error::parse_all;
+use crate::{Answer, ParseError, Result, Solution};
 use nom::{
-    bytes::complete::tag, character::complete, character::complete::newline,
-    multi::separated_list1, IResult,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete,
+    character::complete::{char, newline},
+    combinator::value,
+    multi::separated_list1,
+    IResult,
 };
 use std::collections::HashSet;
-use std::fs;
-
-const SAMPLE_INPUT: &str = "
-R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2
-";
 
+#[derive(Clone, Copy)]
 enum Direction {
     Up,
     Down,
@@ -26,29 +22,29 @@ enum Direction {
 fn parse_steps(input: &str) -> IResult<&str, Vec<(Direction, u32)>> {
     separated_list1(newline, step)(input)
 }
+fn direction(input: &str) -> IResult<&str, Direction> {
+    alt((
+        value(Direction::Up, char('U')),
+        value(Direction::Down, char('D')),
+        value(Direction::Left, char('L')),
+        value(Direction::Right, char('R')),
+    ))(input)
+}
 fn step(input: &str) -> IResult<&str, (Direction, u32)> {
-    let (input, dir) = complete::anychar(input)?;
+    let (input, dir) = direction(input)?;
     let (input, _) = tag(" ")(input)?;
     let (input, count) = complete::u32(input)?;
-    let dir = match dir {
-        'U' => Direction::Up,
-        'D' => Direction::Down,
-        'L' => Direction::Left,
-        'R' => Direction::Right,
-        _ => panic!("Invalid direction code: {dir}"),
-    };
     Ok((input, (dir, count)))
 }
 
-fn part1(input: &str) -> usize {
-    let (_, steps) = parse_steps(input).unwrap();
+fn simulate_short_rope(steps: &[(Direction, u32)]) -> usize {
     // TODO: Rewrite part1 using the more general solution for part2
     let mut hx: i32 = 0;
     let mut hy: i32 = 0;
     let mut tx: i32 = 0;
     let mut ty: i32 = 0;
     let mut tail_positions: HashSet<(i32, i32)> = HashSet::new();
-    for (dir, count) in steps {
+    for &(dir, count) in steps {
         for _ in 0..count {
             match dir {
                 Direction::Up => hy -= 1,
@@ -69,13 +65,12 @@ fn part1(input: &str) -> usize {
     tail_positions.len()
 }
 
-fn part2(input: &str) -> usize {
-    let (_, steps) = parse_steps(input).unwrap();
+fn simulate_long_rope(steps: &[(Direction, u32)]) -> usize {
     let rope_length = 10;
     let mut x_positions: Vec<i32> = vec![0; rope_length];
     let mut y_positions: Vec<i32> = vec![0; rope_length];
     let mut tail_positions: HashSet<(i32, i32)> = HashSet::new();
-    for (dir, count) in steps {
+    for &(dir, count) in steps {
         for _ in 0..count {
             match dir {
                 Direction::Up => y_positions[0] -= 1,
@@ -98,12 +93,55 @@ fn part2(input: &str) -> usize {
     tail_positions.len()
 }
 
-fn main() {
-    let full_input = fs::read_to_string("input/day9").unwrap();
+pub struct Day9 {
+    steps: Vec<(Direction, u32)>,
+}
+
+impl Solution for Day9 {
+    fn parse(input: &str) -> Result<Day9, ParseError> {
+        let steps = parse_all(input, input.trim(), parse_steps)?;
+        Ok(Day9 { steps })
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(simulate_short_rope(&self.steps).into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(simulate_long_rope(&self.steps).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!("Part 1 (sample input): {}", part1(SAMPLE_INPUT.trim()));
-    println!("Part 1 (full input): {}", part1(full_input.trim()));
+    const SAMPLE_INPUT: &str = "
+R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+";
+
+    #[test]
+    fn part1_sample() {
+        let day = Day9::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "13");
+    }
+
+    #[test]
+    fn part2_sample() {
+        let day = Day9::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "1");
+    }
 
-    println!("Part 2 (sample input): {}", part2(SAMPLE_INPUT.trim()));
-    println!("Part 2 (full input): {}", part2(full_input.trim()));
+    #[test]
+    fn part2_larger_sample() {
+        let day = Day9::parse("R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n").unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "36");
+    }
 }
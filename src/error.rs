@@ -0,0 +1,164 @@
+use nom::IResult;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The input could not be parsed.
+    Parse(ParseError),
+    /// The input was parsed, but there is no answer for it.
+    NoSolution(String),
+}
+
+impl Error {
+    pub fn no_solution(message: impl Into<String>) -> Error {
+        Error::NoSolution(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "could not read {}: {source}", path.display()),
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::NoSolution(message) => write!(f, "no solution: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(e) => Some(e),
+            Error::NoSolution(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+/// Where in the input a parse error occurred. Line and column are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub line_text: String,
+}
+
+impl Location {
+    /// Locate `at`, which must be a subslice of `input`.
+    fn find(input: &str, at: &str) -> Location {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .expect("location must be a subslice of the input");
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_text = input[line_start..].lines().next().unwrap_or("");
+        Location {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            line_text: line_text.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub location: Option<Location>,
+    /// Other problems found along with this one.
+    pub more: Vec<ParseError>,
+}
+
+impl ParseError {
+    /// A parse error at the start of `at`, which must be a subslice of `input`.
+    pub fn at(input: &str, at: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            location: Some(Location::find(input, at)),
+            more: Vec::new(),
+        }
+    }
+
+    /// A parse error that concerns the input as a whole.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            location: None,
+            more: Vec::new(),
+        }
+    }
+
+    /// An error reporting all of `errors` at once, if there are any.
+    pub fn combine(errors: impl IntoIterator<Item = ParseError>) -> Result<(), ParseError> {
+        let mut errors = errors.into_iter();
+        match errors.next() {
+            Some(first) => Err(ParseError {
+                more: errors.collect(),
+                ..first
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(Location {
+                line,
+                column,
+                line_text,
+            }) => {
+                writeln!(
+                    f,
+                    "parse error at line {line}, column {column}: {}",
+                    self.message
+                )?;
+                writeln!(f, "  {line_text}")?;
+                write!(f, "  {:>column$}", "^")?;
+            }
+            None => write!(f, "parse error: {}", self.message)?,
+        }
+        for e in &self.more {
+            write!(f, "\n{e}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Run a nom `parser` on `to_parse`, a subslice of `input`, requiring all of it
+/// to be consumed.
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    to_parse: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T, ParseError> {
+    match parser(to_parse) {
+        Ok(("", parsed)) => Ok(parsed),
+        Ok((rest, _)) => Err(ParseError::at(
+            input,
+            rest.trim_start_matches('\n'),
+            "unexpected input",
+        )),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::at(
+            input,
+            e.input,
+            format!("invalid input ({})", e.code.description()),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new("unexpected end of input")),
+    }
+}
@@ -0,0 +1,343 @@
+use crate::error::ParseError;
+use std::ops::{Index, IndexMut};
+
+/// A position in a grid, as `Pos(x, y)` with `Pos(0, 0)` in the top left corner.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct Pos(pub usize, pub usize);
+
+impl Pos {
+    /// The position `(dx, dy)` away from this one, if it doesn't go below zero.
+    pub fn offset(self, (dx, dy): (isize, isize)) -> Option<Pos> {
+        Some(Pos(
+            self.0.checked_add_signed(dx)?,
+            self.1.checked_add_signed(dy)?,
+        ))
+    }
+}
+
+/// Offsets to the left, right, upper and lower neighbours.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Offsets to all eight surrounding neighbours, in reading order.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of values, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    values: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(values: Vec<T>, width: usize, height: usize) -> Grid<T> {
+        assert_eq!(values.len(), width * height, "grid has the wrong size");
+        Grid {
+            values,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Grid<T> {
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Pos(x, y)))
+            .map(&mut f)
+            .collect();
+        Grid::new(values, width, height)
+    }
+
+    pub fn filled(value: T, width: usize, height: usize) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(vec![value; width * height], width, height)
+    }
+
+    /// Parse a grid with one character per cell from the lines of `input`,
+    /// skipping blank lines around it. `parse_cell` returns an error message
+    /// for characters it doesn't accept.
+    ///
+    /// Errors are located in `input`, so pass the whole input rather than a
+    /// trimmed part of it.
+    pub fn parse(
+        input: &str,
+        mut parse_cell: impl FnMut(Pos, char) -> Result<T, String>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut values = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        let lines: Vec<&str> = input.lines().collect();
+        let is_row = |line: &&str| !line.trim().is_empty();
+        let first = lines.iter().position(is_row).unwrap_or(lines.len());
+        let last = lines
+            .iter()
+            .rposition(is_row)
+            .map_or(first, |last| last + 1);
+        for (y, &line) in lines[first..last].iter().enumerate() {
+            let line_width = line.chars().count();
+            if y > 0 && line_width != width {
+                return Err(ParseError::at(input, line, "rows have different lengths"));
+            }
+            width = line_width;
+            height = y + 1;
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let value =
+                    parse_cell(Pos(x, y), c).map_err(|e| ParseError::at(input, &line[i..], e))?;
+                values.push(value);
+            }
+        }
+        if values.is_empty() {
+            return Err(ParseError::new("empty grid"));
+        }
+        Ok(Grid::new(values, width, height))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.width && pos.1 < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self[pos])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self[pos])
+        } else {
+            None
+        }
+    }
+
+    /// All values, row by row.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.values.iter()
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl DoubleEndedIterator<Item = Pos> {
+        let width = self.width;
+        (0..self.values.len()).map(move |i| Pos(i % width, i / width))
+    }
+
+    /// All positions and their values, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.values)
+    }
+
+    /// Positions in row `y`, left to right.
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = Pos> {
+        (0..self.width).map(move |x| Pos(x, y))
+    }
+
+    /// Positions in column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = Pos> {
+        (0..self.height).map(move |y| Pos(x, y))
+    }
+
+    /// Positions on the diagonal through `pos` running from the top left
+    /// towards the bottom right.
+    pub fn diagonal(&self, pos: Pos) -> impl DoubleEndedIterator<Item = Pos> {
+        let back = pos.0.min(pos.1);
+        let start = Pos(pos.0 - back, pos.1 - back);
+        let len = (self.width - start.0).min(self.height - start.1);
+        (0..len).map(move |i| Pos(start.0 + i, start.1 + i))
+    }
+
+    /// Positions on the diagonal through `pos` running from the top right
+    /// towards the bottom left.
+    pub fn anti_diagonal(&self, pos: Pos) -> impl DoubleEndedIterator<Item = Pos> {
+        let back = (self.width - 1 - pos.0).min(pos.1);
+        let start = Pos(pos.0 + back, pos.1 - back);
+        let len = (start.0 + 1).min(self.height - start.1);
+        (0..len).map(move |i| Pos(start.0 - i, start.1 + i))
+    }
+
+    /// Positions reached by repeatedly stepping `step` from `pos` (excluding
+    /// `pos` itself) until leaving the grid.
+    pub fn ray(&self, pos: Pos, step: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        let mut current = pos;
+        std::iter::from_fn(move || {
+            current = current.offset(step).filter(|&p| self.contains(p))?;
+            Some(current)
+        })
+    }
+
+    /// The positions at `offsets` from `pos` that are inside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| pos.offset(offset))
+            .filter(|&p| self.contains(p))
+    }
+
+    /// The left, right, upper and lower neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    /// All eight surrounding neighbours of `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &SURROUNDING)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.values.iter().map(f).collect(), self.width, self.height)
+    }
+
+    /// Mirror the grid in its main diagonal.
+    pub fn transposed(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |Pos(x, y)| self[Pos(y, x)].clone())
+    }
+
+    pub fn rotated_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |Pos(x, y)| {
+            self[Pos(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    pub fn rotated_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |Pos(x, y)| {
+            self[Pos(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Render the grid with one character per cell and a newline after each row.
+    pub fn render(&self, format_value: impl Fn(Pos, &T) -> char) -> String {
+        let mut res = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            for pos in self.row(y) {
+                res.push(format_value(pos, &self[pos]));
+            }
+            res.push('\n');
+        }
+        res
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        &self.values[pos.0 + self.width * pos.1]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        &mut self.values[pos.0 + self.width * pos.1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(input: &str) -> Grid<char> {
+        Grid::parse(input, |_, c| Ok(c)).unwrap()
+    }
+
+    fn render(grid: &Grid<char>) -> String {
+        grid.render(|_, &c| c)
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = letters("\nabc\ndef\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos(2, 0)], 'c');
+        assert_eq!(grid.get(Pos(0, 1)), Some(&'d'));
+        assert_eq!(grid.get(Pos(3, 0)), None);
+        assert_eq!(grid.get(Pos(0, 2)), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let input = "abc\nde\n";
+        let e = Grid::parse(input, |_, c| Ok(c)).unwrap_err();
+        assert_eq!(e.location.unwrap().line, 2);
+
+        let e = Grid::parse("ab\ncd", |_, c| {
+            c.to_digit(10).ok_or(format!("{c} is not a digit"))
+        })
+        .unwrap_err();
+        assert_eq!(e.message, "a is not a digit");
+
+        // Located in the whole input, including the blank lines before the grid
+        let e = Grid::parse("\n  \n12\n3x\n\n", |_, c| {
+            c.to_digit(10).ok_or(format!("{c} is not a digit"))
+        })
+        .unwrap_err();
+        let location = e.location.unwrap();
+        assert_eq!((location.line, location.column), (4, 2));
+    }
+
+    #[test]
+    fn lines() {
+        let grid = letters("abc\ndef\nghi");
+        let at = |positions: Vec<Pos>| positions.into_iter().map(|p| grid[p]).collect::<String>();
+        assert_eq!(at(grid.row(1).collect()), "def");
+        assert_eq!(at(grid.column(2).rev().collect()), "ifc");
+        assert_eq!(at(grid.diagonal(Pos(2, 1)).collect()), "bf");
+        assert_eq!(at(grid.diagonal(Pos(1, 1)).collect()), "aei");
+        assert_eq!(at(grid.anti_diagonal(Pos(0, 1)).collect()), "bd");
+        assert_eq!(at(grid.anti_diagonal(Pos(2, 2)).collect()), "i");
+        assert_eq!(at(grid.ray(Pos(0, 0), (1, 1)).collect()), "ei");
+        assert_eq!(at(grid.ray(Pos(2, 1), (-1, 0)).collect()), "ed");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = letters("abc\ndef\nghi");
+        let at = |positions: Vec<Pos>| positions.into_iter().map(|p| grid[p]).collect::<String>();
+        assert_eq!(at(grid.neighbours4(Pos(1, 1)).collect()), "dfbh");
+        assert_eq!(at(grid.neighbours4(Pos(0, 0)).collect()), "bd");
+        assert_eq!(at(grid.neighbours8(Pos(1, 1)).collect()), "abcdfghi");
+        assert_eq!(at(grid.neighbours8(Pos(2, 2)).collect()), "efh");
+    }
+
+    #[test]
+    fn transform() {
+        let grid = letters("ab\ncd\nef");
+        assert_eq!(render(&grid.transposed()), "ace\nbdf\n");
+        assert_eq!(render(&grid.rotated_clockwise()), "eca\nfdb\n");
+        assert_eq!(render(&grid.rotated_counterclockwise()), "bdf\nace\n");
+        assert_eq!(grid.rotated_clockwise().rotated_counterclockwise(), grid);
+    }
+}
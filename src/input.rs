@@ -0,0 +1,229 @@
+//! Where puzzle inputs come from.
+
+use crate::error::{Error, Result};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the directory with input files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+pub trait InputProvider {
+    /// The raw input for `day`.
+    fn input(&self, day: u32) -> Result<String>;
+}
+
+/// Downloads inputs that aren't available locally, e.g. from the Advent of
+/// Code website using a session token.
+pub trait Fetcher {
+    fn fetch(&self, day: u32) -> Result<String>;
+}
+
+/// Reads inputs from files named `dayN` in a directory. Inputs that are
+/// missing are fetched and saved to the directory, if there is a fetcher.
+pub struct DirectoryProvider {
+    dir: PathBuf,
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl DirectoryProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> DirectoryProvider {
+        DirectoryProvider {
+            dir: dir.into(),
+            fetcher: None,
+        }
+    }
+
+    pub fn with_fetcher(mut self, fetcher: Box<dyn Fetcher>) -> DirectoryProvider {
+        self.fetcher = Some(fetcher);
+        self
+    }
+
+    /// The first of these that is set or exists:
+    ///
+    /// 1. `dir`, typically given on the command line
+    /// 2. the directory in the `AOC_INPUT_DIR` environment variable
+    /// 3. the `input` directory of this repository
+    /// 4. `advent-of-code-2022` in the XDG cache directory
+    pub fn default_dir(dir: Option<&Path>) -> PathBuf {
+        if let Some(dir) = dir {
+            return dir.to_path_buf();
+        }
+        if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+            return dir.into();
+        }
+        let repo_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+        if repo_dir.is_dir() {
+            return repo_dir;
+        }
+        let cache_dir = env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .unwrap_or_else(env::temp_dir);
+        cache_dir.join("advent-of-code-2022")
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day}"))
+    }
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.into(),
+        source,
+    })
+}
+
+impl InputProvider for DirectoryProvider {
+    fn input(&self, day: u32) -> Result<String> {
+        let path = self.path(day);
+        match (&self.fetcher, path.exists()) {
+            (Some(fetcher), false) => {
+                let input = fetcher.fetch(day)?;
+                let io_error = |source| Error::Io {
+                    path: path.clone(),
+                    source,
+                };
+                fs::create_dir_all(&self.dir).map_err(io_error)?;
+                fs::write(&path, &input).map_err(io_error)?;
+                Ok(input)
+            }
+            _ => read_file(&path),
+        }
+    }
+}
+
+/// Reads the same file for every day.
+pub struct FileProvider {
+    path: PathBuf,
+}
+
+impl FileProvider {
+    pub fn new(path: impl Into<PathBuf>) -> FileProvider {
+        FileProvider { path: path.into() }
+    }
+}
+
+impl InputProvider for FileProvider {
+    fn input(&self, _day: u32) -> Result<String> {
+        read_file(&self.path)
+    }
+}
+
+/// Reads standard input once, and uses it for every day.
+#[derive(Default)]
+pub struct StdinProvider {
+    input: OnceCell<String>,
+}
+
+impl InputProvider for StdinProvider {
+    fn input(&self, _day: u32) -> Result<String> {
+        if let Some(input) = self.input.get() {
+            return Ok(input.clone());
+        }
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|source| Error::Io {
+                path: "<stdin>".into(),
+                source,
+            })?;
+        Ok(self.input.get_or_init(|| input).clone())
+    }
+}
+
+/// Inputs kept in memory, e.g. for tests.
+#[derive(Default)]
+pub struct MemoryProvider {
+    inputs: HashMap<u32, String>,
+}
+
+impl MemoryProvider {
+    pub fn insert(&mut self, day: u32, input: impl Into<String>) {
+        self.inputs.insert(day, input.into());
+    }
+}
+
+impl InputProvider for MemoryProvider {
+    fn input(&self, day: u32) -> Result<String> {
+        self.inputs.get(&day).cloned().ok_or_else(|| Error::Io {
+            path: format!("day{day}").into(),
+            source: io::ErrorKind::NotFound.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// A stand-in for a downloader, serving inputs from memory and counting
+    /// how many times it was asked.
+    struct FakeFetcher {
+        inputs: MemoryProvider,
+        fetches: Rc<Cell<usize>>,
+    }
+
+    impl Fetcher for FakeFetcher {
+        fn fetch(&self, day: u32) -> Result<String> {
+            self.fetches.set(self.fetches.get() + 1);
+            self.inputs.input(day)
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-2022-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn memory_provider() {
+        let mut provider = MemoryProvider::default();
+        provider.insert(3, "abc");
+        assert_eq!(provider.input(3).unwrap(), "abc");
+        assert!(matches!(provider.input(4), Err(Error::Io { .. })));
+    }
+
+    #[test]
+    fn directory_provider_reads_files() {
+        let dir = temp_dir("read");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1"), "1000\n2000\n").unwrap();
+        let provider = DirectoryProvider::new(&dir);
+        assert_eq!(provider.input(1).unwrap(), "1000\n2000\n");
+        assert!(matches!(provider.input(2), Err(Error::Io { .. })));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn directory_provider_caches_fetched_inputs() {
+        let dir = temp_dir("fetch");
+        let mut inputs = MemoryProvider::default();
+        inputs.insert(6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let fetches = Rc::new(Cell::new(0));
+        let provider = DirectoryProvider::new(&dir).with_fetcher(Box::new(FakeFetcher {
+            inputs,
+            fetches: fetches.clone(),
+        }));
+
+        assert_eq!(provider.input(6).unwrap(), "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(provider.input(6).unwrap(), "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(fetches.get(), 1);
+        assert!(dir.join("day6").exists());
+        assert!(provider.input(7).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn explicit_dir_comes_first() {
+        let dir = Path::new("somewhere");
+        assert_eq!(DirectoryProvider::default_dir(Some(dir)), dir);
+    }
+}
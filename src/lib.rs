@@ -0,0 +1,61 @@
+pub mod answers;
+pub mod bench;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+
+pub use error::{Error, ParseError, Result};
+pub use solution::{Answer, Solution};
+
+/// A registered puzzle solution.
+pub struct Day {
+    pub day: u32,
+    pub parse: fn(&str) -> Result<Box<dyn Solution>, ParseError>,
+}
+
+fn parse_boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, ParseError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+macro_rules! day {
+    ($day:literal, $module:ident :: $solution:ident) => {
+        Day {
+            day: $day,
+            parse: parse_boxed::<$module::$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, day1::Day1),
+    day!(2, day2::Day2),
+    day!(3, day3::Day3),
+    day!(4, day4::Day4),
+    day!(5, day5::Day5),
+    day!(6, day6::Day6),
+    day!(7, day7::Day7),
+    day!(8, day8::Day8),
+    day!(9, day9::Day9),
+    day!(10, day10::Day10),
+    day!(11, day11::Day11),
+    day!(12, day12::Day12),
+];
+
+pub fn get_day(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,225 @@
+//! Creating the files for a new day from `templates/day.rs`.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+const DAYS_START: &str = "pub const DAYS: &[Day] = &[";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day number is not a day of Advent of Code.
+    InvalidDay(u32),
+    /// The day already has a module or is already registered.
+    Exists(PathBuf),
+    /// `src/lib.rs` doesn't look like expected.
+    Unregistrable(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "there is no day {day}"),
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Unregistrable(message) => {
+                write!(f, "could not register the day in src/lib.rs: {message}")
+            }
+            ScaffoldError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> ScaffoldError + '_ {
+    |source| ScaffoldError::Io {
+        path: path.into(),
+        source,
+    }
+}
+
+/// The template with its `Day?` placeholders filled in.
+pub fn module_source(day: u32) -> String {
+    TEMPLATE.replace("Day?", &format!("Day{day}"))
+}
+
+/// Add `pub mod dayN;` and the `DAYS` entry for `day` to the source of
+/// `src/lib.rs`, keeping both lists in the order rustfmt and the runner use.
+pub fn register(lib_rs: &str, day: u32) -> Result<String, ScaffoldError> {
+    let name = format!("day{day}");
+    let module = format!("pub mod {name};");
+    let entry = format!("    day!({day}, day{day}::Day{day}),");
+    if lib_rs.lines().any(|line| line == module) {
+        return Err(ScaffoldError::Exists(format!("src/day{day}.rs").into()));
+    }
+
+    let mut lines: Vec<&str> = lib_rs.lines().collect();
+    // Modules are sorted by name
+    let mods = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod "))
+        .ok_or_else(|| ScaffoldError::Unregistrable("no modules found".to_string()))?;
+    let mod_index = lines[mods..]
+        .iter()
+        .position(|line| match line.strip_prefix("pub mod ") {
+            Some(other) => other.trim_end_matches(';') > name.as_str(),
+            None => true,
+        })
+        .map_or(lines.len(), |i| mods + i);
+    lines.insert(mod_index, &module);
+
+    // Days are sorted by number
+    let days_start = lines
+        .iter()
+        .position(|line| *line == DAYS_START)
+        .ok_or_else(|| ScaffoldError::Unregistrable(format!("`{DAYS_START}` not found")))?;
+    let days_end = lines[days_start..]
+        .iter()
+        .position(|line| *line == "];")
+        .map(|i| days_start + i)
+        .ok_or_else(|| ScaffoldError::Unregistrable("end of DAYS not found".to_string()))?;
+    let entry_index = (days_start + 1..days_end)
+        .find(|&i| registered_day(lines[i]).is_some_and(|d| d > day))
+        .unwrap_or(days_end);
+    lines.insert(entry_index, &entry);
+
+    let mut res = lines.join("\n");
+    res.push('\n');
+    Ok(res)
+}
+
+/// The day number of a `day!(N, ...)` line.
+fn registered_day(line: &str) -> Option<u32> {
+    let rest = line.trim().strip_prefix("day!(")?;
+    rest[..rest.find(',')?].parse().ok()
+}
+
+/// Create `src/dayN.rs` in the repository at `root`, register it in
+/// `src/lib.rs` and create an empty input file in `input_dir` unless there
+/// is one already. Returns the paths that were written.
+pub fn scaffold(root: &Path, input_dir: &Path, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let module_path = root.join(format!("src/day{day}.rs"));
+    if module_path.exists() {
+        return Err(ScaffoldError::Exists(module_path));
+    }
+    // Prepare the registration before writing anything, so that a day that
+    // can't be registered leaves no files behind
+    let lib_path = root.join("src/lib.rs");
+    let lib_rs = fs::read_to_string(&lib_path).map_err(io_error(&lib_path))?;
+    let lib_rs = register(&lib_rs, day)?;
+
+    fs::write(&module_path, module_source(day)).map_err(io_error(&module_path))?;
+    if let Err(e) = fs::write(&lib_path, lib_rs) {
+        // An unregistered module would make the next attempt refuse to run
+        let _ = fs::remove_file(&module_path);
+        return Err(io_error(&lib_path)(e));
+    }
+    let mut written = vec![module_path, lib_path];
+
+    let input_path = input_dir.join(format!("day{day}"));
+    if !input_path.exists() {
+        fs::create_dir_all(input_dir).map_err(io_error(input_dir))?;
+        fs::write(&input_path, "").map_err(io_error(&input_path))?;
+        written.push(input_path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB_RS: &str = "pub mod answers;
+pub mod day1;
+pub mod day12;
+pub mod day2;
+pub mod error;
+
+pub const DAYS: &[Day] = &[
+    day!(1, day1::Day1),
+    day!(2, day2::Day2),
+    day!(12, day12::Day12),
+];
+";
+
+    #[test]
+    fn registers_in_order() {
+        assert_eq!(
+            register(LIB_RS, 3).unwrap(),
+            "pub mod answers;
+pub mod day1;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod error;
+
+pub const DAYS: &[Day] = &[
+    day!(1, day1::Day1),
+    day!(2, day2::Day2),
+    day!(3, day3::Day3),
+    day!(12, day12::Day12),
+];
+"
+        );
+        let lib_rs = register(LIB_RS, 13).unwrap();
+        assert!(lib_rs.contains("pub mod day12;\npub mod day13;\npub mod day2;"));
+        assert!(lib_rs.contains("day!(12, day12::Day12),\n    day!(13, day13::Day13),\n];"));
+    }
+
+    #[test]
+    fn refuses_registered_days() {
+        assert!(matches!(register(LIB_RS, 2), Err(ScaffoldError::Exists(_))));
+    }
+
+    #[test]
+    fn scaffolds_files() {
+        let root = std::env::temp_dir().join(format!("aoc-2022-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB_RS).unwrap();
+        let input_dir = root.join("input");
+
+        let written = scaffold(&root, &input_dir, 13).unwrap();
+        assert_eq!(written.len(), 3);
+        let module = fs::read_to_string(root.join("src/day13.rs")).unwrap();
+        assert!(module.contains("impl Solution for Day13 {"));
+        assert!(!module.contains("Day?"));
+        // The placeholder sample tests only run once they are filled in
+        assert_eq!(module.matches("#[ignore").count(), 2);
+        assert_eq!(fs::read_to_string(input_dir.join("day13")).unwrap(), "");
+
+        assert!(matches!(
+            scaffold(&root, &input_dir, 13),
+            Err(ScaffoldError::Exists(_))
+        ));
+        assert!(matches!(
+            scaffold(&root, &input_dir, 26),
+            Err(ScaffoldError::InvalidDay(26))
+        ));
+
+        fs::write(root.join("src/lib.rs"), "// no modules\n").unwrap();
+        assert!(matches!(
+            scaffold(&root, &input_dir, 14),
+            Err(ScaffoldError::Unregistrable(_))
+        ));
+        assert!(!root.join("src/day14.rs").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
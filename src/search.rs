@@ -0,0 +1,315 @@
+//! Shortest path searches over any graph, given as a start node and a function
+//! returning the neighbours of a node.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The outcome of a search: the goal if one was found, along with the
+/// distances and predecessors of every node reached on the way.
+#[derive(Debug)]
+pub struct SearchResult<N> {
+    pub goal: Option<N>,
+    /// Nodes whose neighbours were examined, in the order they were expanded.
+    pub explored: Vec<N>,
+    distances: HashMap<N, usize>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+{
+    fn new(start: N) -> SearchResult<N> {
+        SearchResult {
+            goal: None,
+            explored: Vec::new(),
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// Distance from the start to the goal.
+    pub fn distance(&self) -> Option<usize> {
+        self.distance_to(self.goal.as_ref()?)
+    }
+
+    /// Shortest path from the start to the goal, including both.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// Shortest known distance from the start to `node`. This is only the
+    /// shortest possible distance for explored nodes.
+    pub fn distance_to(&self, node: &N) -> Option<usize> {
+        self.distances.get(node).copied()
+    }
+
+    /// Shortest known path from the start to `node`, including both.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        let mut current = node;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The set of explored nodes.
+    pub fn explored_set(&self) -> HashSet<&N> {
+        self.explored.iter().collect()
+    }
+
+    /// Record reaching `node` from `from` at `distance`, if that is shorter
+    /// than any previous way there.
+    fn relax(&mut self, from: &N, node: &N, distance: usize) -> bool {
+        match self.distances.get(node) {
+            Some(&known) if known <= distance => false,
+            _ => {
+                self.distances.insert(node.clone(), distance);
+                self.predecessors.insert(node.clone(), from.clone());
+                true
+            }
+        }
+    }
+}
+
+/// Breadth-first search, where every edge has length 1.
+///
+/// The search stops at the first node for which `is_goal` returns true, or
+/// when every reachable node has been explored.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new(start.clone());
+    let mut fringe = VecDeque::from([start]);
+    while let Some(current) = fringe.pop_front() {
+        result.explored.push(current.clone());
+        if is_goal(&current) {
+            result.goal = Some(current);
+            return result;
+        }
+        let distance = result.distances[&current] + 1;
+        for neighbour in neighbours(&current) {
+            if !result.distances.contains_key(&neighbour) {
+                result.relax(&current, &neighbour, distance);
+                fringe.push_back(neighbour);
+            }
+        }
+    }
+    result
+}
+
+/// Dijkstra's algorithm. `neighbours` returns each neighbour along with the
+/// length of the edge to it.
+pub fn dijkstra<N, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+struct FringeEntry<N> {
+    estimate: usize,
+    distance: usize,
+    node: N,
+}
+
+impl<N> PartialEq for FringeEntry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N> Eq for FringeEntry<N> {}
+
+impl<N> PartialOrd for FringeEntry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for FringeEntry<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, to make the BinaryHeap pop the lowest estimate first
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+/// A* search. `heuristic` estimates the remaining distance from a node to the
+/// goal, and must never overestimate it for the result to be a shortest path.
+pub fn astar<N, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> usize,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar_observed(start, neighbours, heuristic, is_goal, |_, _| {})
+}
+
+/// A* search, calling `on_expand` after expanding each node other than the
+/// goal, with the search so far and the nodes waiting to be expanded.
+pub fn astar_observed<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut on_expand: impl FnMut(&SearchResult<N>, &mut dyn Iterator<Item = &N>),
+) -> SearchResult<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    // A* algorithm adapted from Wikipedia
+    let mut result = SearchResult::new(start.clone());
+    let mut fringe = BinaryHeap::from([FringeEntry {
+        estimate: heuristic(&start),
+        distance: 0,
+        node: start,
+    }]);
+    while let Some(FringeEntry { distance, node, .. }) = fringe.pop() {
+        if distance > result.distances[&node] {
+            // A shorter path to this node was found after this entry was added
+            continue;
+        }
+        result.explored.push(node.clone());
+        if is_goal(&node) {
+            result.goal = Some(node);
+            return result;
+        }
+        for (neighbour, length) in neighbours(&node) {
+            let tentative_distance = distance + length;
+            if result.relax(&node, &neighbour, tentative_distance) {
+                fringe.push(FringeEntry {
+                    estimate: tentative_distance + heuristic(&neighbour),
+                    distance: tentative_distance,
+                    node: neighbour,
+                });
+            }
+        }
+        // Entries for nodes that were since reached by a shorter path are
+        // still in the fringe, but won't be expanded
+        let mut waiting = fringe
+            .iter()
+            .filter(|entry| entry.distance <= result.distances[&entry.node])
+            .map(|entry| &entry.node);
+        on_expand(&result, &mut waiting);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small weighted graph:
+    //
+    //   0 --1-- 1 --1-- 2
+    //   |               |
+    //   5               1
+    //   |               |
+    //   4 ------1------ 3      5 (unreachable)
+    fn edges(node: &u32) -> Vec<(u32, usize)> {
+        match node {
+            0 => vec![(1, 1), (4, 5)],
+            1 => vec![(0, 1), (2, 1)],
+            2 => vec![(1, 1), (3, 1)],
+            3 => vec![(2, 1), (4, 1)],
+            4 => vec![(0, 5), (3, 1)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted(node: &u32) -> Vec<u32> {
+        edges(node).into_iter().map(|(n, _)| n).collect()
+    }
+
+    #[test]
+    fn bfs_counts_edges() {
+        let result = bfs(0, unweighted, |&n| n == 3);
+        assert_eq!(result.distance(), Some(2));
+        assert_eq!(result.path(), Some(vec![0, 4, 3]));
+        assert_eq!(result.explored.first(), Some(&0));
+        assert_eq!(result.explored.last(), Some(&3));
+    }
+
+    #[test]
+    fn dijkstra_uses_edge_lengths() {
+        let result = dijkstra(0, edges, |&n| n == 4);
+        assert_eq!(result.distance(), Some(4));
+        assert_eq!(result.path(), Some(vec![0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let heuristic = |&n: &u32| match n {
+            0 => 4,
+            1 => 3,
+            2 => 2,
+            3 => 1,
+            _ => 0,
+        };
+        let result = astar(0, edges, heuristic, |&n| n == 4);
+        assert_eq!(result.distance(), Some(4));
+        assert_eq!(result.path(), Some(vec![0, 1, 2, 3, 4]));
+        assert!(!result.explored_set().contains(&5));
+    }
+
+    #[test]
+    fn observed_fringe() {
+        let mut fringes = Vec::new();
+        let result = astar_observed(
+            0,
+            edges,
+            |_| 0,
+            |&n| n == 4,
+            |result, fringe| {
+                let mut fringe: Vec<_> = fringe.copied().collect();
+                fringe.sort();
+                fringes.push((result.explored.len(), fringe));
+            },
+        );
+        assert_eq!(result.distance(), Some(4));
+        // Node 4 is first reached from 0 with distance 5, then from 3 with 4
+        assert_eq!(
+            fringes,
+            [
+                (1, vec![1, 4]),
+                (2, vec![2, 4]),
+                (3, vec![3, 4]),
+                (4, vec![4])
+            ]
+        );
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let result = dijkstra(0, edges, |&n| n == 5);
+        assert_eq!(result.goal, None);
+        assert_eq!(result.distance(), None);
+        assert_eq!(result.explored.len(), 5);
+        assert_eq!(result.distance_to(&3), Some(3));
+        assert_eq!(result.path_to(&4), Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(result.path_to(&5), None);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{Answer, ParseError, Result, Solution};
+
+pub struct Day? {}
+
+impl Solution for Day? {
+    fn parse(_input: &str) -> Result<Day?, ParseError> {
+        Ok(Day? {})
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(0.into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(0.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "
+";
+
+    #[test]
+    #[ignore = "fill in the sample input and its answer"]
+    fn part1_sample() {
+        let day = Day?::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part1().unwrap().to_string(), "");
+    }
+
+    #[test]
+    #[ignore = "fill in the sample input and its answer"]
+    fn part2_sample() {
+        let day = Day?::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(day.part2().unwrap().to_string(), "");
+    }
+}